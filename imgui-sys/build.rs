@@ -43,6 +43,7 @@ fn main() -> std::io::Result<()> {
     let docking_enabled = std::env::var_os("CARGO_FEATURE_DOCKING").is_some();
     let freetype_enabled = std::env::var_os("CARGO_FEATURE_FREETYPE").is_some();
     let wasm_enabled = std::env::var_os("CARGO_FEATURE_WASM").is_some();
+    let test_engine_hooks_enabled = std::env::var_os("CARGO_FEATURE_TEST_ENGINE_HOOKS").is_some();
 
     let cimgui_dir = manifest_dir.join(match (docking_enabled, freetype_enabled) {
         (false, false) => "third-party/imgui-master",
@@ -92,6 +93,15 @@ fn main() -> std::io::Result<()> {
             build.include(dbg!(cimgui_dir.join("imgui")));
         }
 
+        // Test engine hooks: register items with the callbacks in
+        // include_test_engine_hooks.cpp. This doesn't change any struct layouts.
+        if test_engine_hooks_enabled {
+            build.define("IMGUI_ENABLE_TEST_ENGINE", None);
+            println!("cargo:DEFINE_IMGUI_ENABLE_TEST_ENGINE=");
+            build.include(cimgui_dir.join("imgui"));
+            build.file("include_test_engine_hooks.cpp");
+        }
+
         // Which "all imgui" file to use
         let imgui_cpp = match (docking_enabled, freetype_enabled) {
             (false, false) => "include_imgui_master.cpp",
@@ -0,0 +1,57 @@
+// Implementation of the dear imgui test engine hooks (enabled with IMGUI_ENABLE_TEST_ENGINE),
+// forwarding item registration to C callbacks so they can be consumed from Rust.
+//
+// The callbacks are stored per context in ImGuiContext::TestEngine.
+#include "imgui.h"
+#include "imgui_internal.h"
+
+extern "C" {
+
+typedef struct ImGuiRsTestEngineHooks
+{
+    void* UserData;
+    // Called from ItemAdd() for every item with an ID, including clipped ones, and for windows.
+    void (*ItemAdd)(void* user_data, ImGuiID id, float min_x, float min_y, float max_x, float max_y, int status_flags);
+    // Called by most widgets after their behavior was processed, with the label of the item.
+    void (*ItemInfo)(void* user_data, ImGuiID id, const char* label, int status_flags);
+} ImGuiRsTestEngineHooks;
+
+// Registers hooks on a context. Passing NULL disables them. The hooks must outlive the registration.
+void ImGuiRsTestEngine_SetHooks(ImGuiContext* ctx, ImGuiRsTestEngineHooks* hooks)
+{
+    ctx->TestEngine = hooks;
+    ctx->TestEngineHookItems = hooks != NULL;
+}
+
+// Returns the active, hovered and keyboard/gamepad focused item IDs of a context.
+void ImGuiRsTestEngine_GetItemIds(ImGuiContext* ctx, ImGuiID* active_id, ImGuiID* hovered_id, ImGuiID* focused_id)
+{
+    *active_id = ctx->ActiveId;
+    *hovered_id = ctx->HoveredId;
+    *focused_id = ctx->NavId;
+}
+
+}
+
+void ImGuiTestEngineHook_ItemAdd(ImGuiContext* ctx, ImGuiID id, const ImRect& bb, const ImGuiLastItemData* item_data)
+{
+    ImGuiRsTestEngineHooks* hooks = (ImGuiRsTestEngineHooks*)ctx->TestEngine;
+    if (hooks && hooks->ItemAdd)
+        hooks->ItemAdd(hooks->UserData, id, bb.Min.x, bb.Min.y, bb.Max.x, bb.Max.y, item_data ? item_data->StatusFlags : 0);
+}
+
+void ImGuiTestEngineHook_ItemInfo(ImGuiContext* ctx, ImGuiID id, const char* label, ImGuiItemStatusFlags flags)
+{
+    ImGuiRsTestEngineHooks* hooks = (ImGuiRsTestEngineHooks*)ctx->TestEngine;
+    if (hooks && hooks->ItemInfo)
+        hooks->ItemInfo(hooks->UserData, id, label, flags);
+}
+
+void ImGuiTestEngineHook_Log(ImGuiContext*, const char*, ...)
+{
+}
+
+const char* ImGuiTestEngine_FindItemDebugLabel(ImGuiContext*, ImGuiID)
+{
+    return NULL;
+}
@@ -52,6 +52,12 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(feature = "test-engine-hooks")]
+#[allow(non_snake_case)]
+mod test_engine_hooks;
+#[cfg(feature = "test-engine-hooks")]
+pub use crate::test_engine_hooks::*;
+
 impl ImVec2 {
     #[inline]
     pub const fn new(x: f32, y: f32) -> ImVec2 {
@@ -0,0 +1,39 @@
+//! Declarations for `include_test_engine_hooks.cpp`, which implements the dear imgui test engine
+//! hooks (`IMGUI_ENABLE_TEST_ENGINE`) by forwarding them to C callbacks.
+
+use crate::{ImGuiContext, ImGuiID};
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ImGuiRsTestEngineHooks {
+    pub UserData: *mut core::ffi::c_void,
+    pub ItemAdd: Option<
+        unsafe extern "C" fn(
+            user_data: *mut core::ffi::c_void,
+            id: ImGuiID,
+            min_x: f32,
+            min_y: f32,
+            max_x: f32,
+            max_y: f32,
+            status_flags: core::ffi::c_int,
+        ),
+    >,
+    pub ItemInfo: Option<
+        unsafe extern "C" fn(
+            user_data: *mut core::ffi::c_void,
+            id: ImGuiID,
+            label: *const core::ffi::c_char,
+            status_flags: core::ffi::c_int,
+        ),
+    >,
+}
+
+extern "C" {
+    pub fn ImGuiRsTestEngine_SetHooks(ctx: *mut ImGuiContext, hooks: *mut ImGuiRsTestEngineHooks);
+    pub fn ImGuiRsTestEngine_GetItemIds(
+        ctx: *mut ImGuiContext,
+        active_id: *mut ImGuiID,
+        hovered_id: *mut ImGuiID,
+        focused_id: *mut ImGuiID,
+    );
+}
@@ -0,0 +1,654 @@
+//! Scripted UI automation for headless integration tests.
+//!
+//! A [`Driver`] runs UI code in a [`Harness`] and drives it like a user would: it locates items by
+//! their ID path, moves the mouse to them, clicks, types text and presses keys by queuing input
+//! events through the `Io::add_*_event` functions, one frame at a time. After each frame, the
+//! state of every submitted item (its rect, whether it is hovered, active, checked...) can be
+//! inspected.
+//!
+//! Items are discovered through the dear imgui test engine hooks, so this module requires the
+//! `automation` feature.
+//!
+//! ```no_run
+//! use imgui::automation::Driver;
+//! use imgui::testing::Harness;
+//!
+//! let mut muted = false;
+//! let mut driver = Driver::new(Harness::new([640.0, 480.0]), |ui| {
+//!     ui.window("Settings").build(|| {
+//!         if let Some(_tab_bar) = ui.tab_bar("##tabs") {
+//!             if let Some(_tab) = ui.tab_item("Audio") {
+//!                 ui.checkbox("Mute", &mut muted);
+//!             }
+//!         }
+//!     });
+//! });
+//! driver.click("Settings/##tabs/Audio").unwrap();
+//! driver.click("Settings/##tabs/Audio/Mute").unwrap();
+//! assert_eq!(driver.item("Settings/##tabs/Audio/Mute").unwrap().is_checked(), Some(true));
+//! ```
+//!
+//! # Item paths
+//!
+//! A path is a list of `/` separated components, starting with the name of a window and followed
+//! by the IDs pushed onto the ID stack, ending with the item's own label or ID. For example
+//! `"Settings/##tabs/Audio"` is the `Audio` tab of the `##tabs` tab bar in the `Settings` window.
+//!
+//! - a component is hashed exactly like dear imgui hashes labels, so `"Save###save"` and
+//!   `"###save"` refer to the same item,
+//! - `$$` followed by a number refers to an ID pushed with [`Ui::push_id_int`], e.g. `"List/$$3/Remove"`,
+//! - a literal `/` in a component is written `\/`.
+//!
+//! Child windows get their own ID which is not derived from the parent window, so items inside
+//! child windows cannot be addressed by path. Use [`Driver::item_by_label`] for those.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::ffi::{c_void, CStr};
+use std::fmt;
+use std::os::raw::{c_char, c_int};
+
+use bitflags::bitflags;
+
+use crate::input::keyboard::{Key, KeyChord, KeyMod};
+use crate::input::mouse::MouseButton;
+use crate::testing::{Harness, InputEvent};
+use crate::{sys, Id, Ui};
+
+bitflags! {
+    /// Status of an item, as reported by dear imgui when the item was submitted
+    #[repr(transparent)]
+    pub struct ItemStatusFlags: u32 {
+        /// Mouse position is within the item rectangle
+        const HOVERED_RECT = 1 << 0;
+        /// Value exposed by item was edited in the current frame
+        const EDITED = 1 << 2;
+        /// Selection was toggled in the current frame (e.g. a selectable)
+        const TOGGLED_SELECTION = 1 << 3;
+        /// Open state was toggled in the current frame (e.g. a tree node)
+        const TOGGLED_OPEN = 1 << 4;
+        /// Item was deactivated in the current frame
+        const DEACTIVATED = 1 << 6;
+        /// Item overlaps the current clipping rectangle
+        const VISIBLE = 1 << 8;
+        /// Item can be opened (e.g. a tree node)
+        const OPENABLE = 1 << 20;
+        /// Item is open
+        const OPENED = 1 << 21;
+        /// Item can be checked (e.g. a checkbox or menu item)
+        const CHECKABLE = 1 << 22;
+        /// Item is checked
+        const CHECKED = 1 << 23;
+        /// Item accepts text input (e.g. an input text, slider or drag)
+        const INPUTABLE = 1 << 24;
+    }
+}
+
+/// The state of an item on the last frame run by a [`Driver`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemInfo {
+    id: Id,
+    label: Option<String>,
+    rect_min: [f32; 2],
+    rect_max: [f32; 2],
+    status: ItemStatusFlags,
+    hovered: bool,
+    active: bool,
+    focused: bool,
+}
+
+impl ItemInfo {
+    /// Returns the ID of the item.
+    #[inline]
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Returns the full label of the item (including any `##` suffix), if the widget reported one.
+    #[inline]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Returns the upper-left bounding rectangle corner of the item in screen space.
+    #[inline]
+    pub fn rect_min(&self) -> [f32; 2] {
+        self.rect_min
+    }
+
+    /// Returns the lower-right bounding rectangle corner of the item in screen space.
+    #[inline]
+    pub fn rect_max(&self) -> [f32; 2] {
+        self.rect_max
+    }
+
+    /// Returns the size of the item.
+    #[inline]
+    pub fn rect_size(&self) -> [f32; 2] {
+        [
+            self.rect_max[0] - self.rect_min[0],
+            self.rect_max[1] - self.rect_min[1],
+        ]
+    }
+
+    /// Returns the centre of the item, where [`Driver::click`] clicks.
+    #[inline]
+    pub fn center(&self) -> [f32; 2] {
+        [
+            (self.rect_min[0] + self.rect_max[0]) * 0.5,
+            (self.rect_min[1] + self.rect_max[1]) * 0.5,
+        ]
+    }
+
+    /// Returns the status flags reported for the item.
+    #[inline]
+    pub fn status(&self) -> ItemStatusFlags {
+        self.status
+    }
+
+    /// Returns `true` if the item is hovered.
+    #[inline]
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    /// Returns `true` if the item is active (e.g. a button being held or a text input being
+    /// edited).
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Returns `true` if the item has keyboard/gamepad navigation focus.
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Returns `true` if the item value was edited on the last frame.
+    #[inline]
+    pub fn is_edited(&self) -> bool {
+        self.status.contains(ItemStatusFlags::EDITED)
+    }
+
+    /// Returns whether a checkable item is checked, or `None` for other items.
+    #[inline]
+    pub fn is_checked(&self) -> Option<bool> {
+        self.status
+            .contains(ItemStatusFlags::CHECKABLE)
+            .then(|| self.status.contains(ItemStatusFlags::CHECKED))
+    }
+
+    /// Returns whether an openable item (e.g. a tree node) is open, or `None` for other items.
+    #[inline]
+    pub fn is_open(&self) -> Option<bool> {
+        self.status
+            .contains(ItemStatusFlags::OPENABLE)
+            .then(|| self.status.contains(ItemStatusFlags::OPENED))
+    }
+}
+
+/// An error returned by [`Driver`] actions
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AutomationError {
+    /// No item with this path or label was submitted on the last frame
+    ItemNotFound(String),
+}
+
+impl fmt::Display for AutomationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AutomationError::ItemNotFound(path) => write!(f, "Item not found: {:?}", path),
+        }
+    }
+}
+
+impl Error for AutomationError {}
+
+/// Computes the ID of an item from its path. See the [module documentation](self) for the syntax.
+pub fn id_from_path(path: &str) -> Id {
+    let mut id = 0;
+    let mut component = String::new();
+    let mut chars = path.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'/') => component.push(chars.next().unwrap()),
+            '/' => {
+                id = hash_component(&component, id);
+                component.clear();
+            }
+            c => component.push(c),
+        }
+    }
+    Id(hash_component(&component, id))
+}
+
+fn hash_component(component: &str, seed: u32) -> u32 {
+    match component
+        .strip_prefix("$$")
+        .and_then(|n| n.parse::<i32>().ok())
+    {
+        Some(n) => hash_data(&n.to_ne_bytes(), seed),
+        None => hash_str(component, seed),
+    }
+}
+
+/// Equivalent of `ImHashData`.
+fn hash_data(data: &[u8], seed: u32) -> u32 {
+    let mut crc = !seed;
+    for &byte in data {
+        crc = (crc >> 8) ^ CRC32_TABLE[((crc & 0xff) as u8 ^ byte) as usize];
+    }
+    !crc
+}
+
+/// Equivalent of `ImHashStr`, where `###` resets the hash to the seed.
+fn hash_str(s: &str, seed: u32) -> u32 {
+    let seed = !seed;
+    let mut crc = seed;
+    let bytes = s.as_bytes();
+    for (i, &byte) in bytes.iter().enumerate() {
+        if bytes[i..].starts_with(b"###") {
+            crc = seed;
+        }
+        crc = (crc >> 8) ^ CRC32_TABLE[((crc & 0xff) as u8 ^ byte) as usize];
+    }
+    !crc
+}
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Items collected by the test engine hooks during a frame.
+struct ItemRecorder {
+    hooks: sys::ImGuiRsTestEngineHooks,
+    items: HashMap<u32, ItemInfo>,
+    order: Vec<u32>,
+}
+
+unsafe extern "C" fn item_add(
+    user_data: *mut c_void,
+    id: sys::ImGuiID,
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+    status_flags: c_int,
+) {
+    let recorder = &mut *(user_data as *mut ItemRecorder);
+    // Items registered twice in a frame (e.g. by ItemAdd and ButtonBehavior) keep their first
+    // rect, which is the one used for hit testing.
+    recorder.items.entry(id).or_insert_with(|| {
+        recorder.order.push(id);
+        ItemInfo {
+            id: Id(id),
+            label: None,
+            rect_min: [min_x, min_y],
+            rect_max: [max_x, max_y],
+            status: ItemStatusFlags::from_bits_truncate(status_flags as u32),
+            hovered: false,
+            active: false,
+            focused: false,
+        }
+    });
+}
+
+unsafe extern "C" fn item_info(
+    user_data: *mut c_void,
+    id: sys::ImGuiID,
+    label: *const c_char,
+    status_flags: c_int,
+) {
+    let recorder = &mut *(user_data as *mut ItemRecorder);
+    if let Some(item) = recorder.items.get_mut(&id) {
+        if !label.is_null() {
+            item.label = Some(CStr::from_ptr(label).to_string_lossy().into_owned());
+        }
+        item.status |= ItemStatusFlags::from_bits_truncate(status_flags as u32);
+    }
+}
+
+/// Drives UI code with simulated user input. See the [module documentation](self).
+///
+/// Every action runs one or more frames of the UI closure. Item queries always refer to the
+/// items submitted on the last frame.
+pub struct Driver<'a> {
+    harness: Harness,
+    ui_fn: Box<dyn FnMut(&Ui) + 'a>,
+    // Owned, registered with the context in `ctx.TestEngine` until the driver is dropped.
+    recorder: *mut ItemRecorder,
+    items: HashMap<u32, ItemInfo>,
+    order: Vec<u32>,
+}
+
+impl<'a> Driver<'a> {
+    /// Creates a driver for the UI closure and runs a first frame.
+    pub fn new<F>(harness: Harness, ui_fn: F) -> Self
+    where
+        F: FnMut(&Ui) + 'a,
+    {
+        let recorder = Box::into_raw(Box::new(ItemRecorder {
+            hooks: sys::ImGuiRsTestEngineHooks {
+                UserData: std::ptr::null_mut(),
+                ItemAdd: Some(item_add),
+                ItemInfo: Some(item_info),
+            },
+            items: HashMap::new(),
+            order: Vec::new(),
+        }));
+        // The harness owns the current context.
+        unsafe {
+            (*recorder).hooks.UserData = recorder as *mut c_void;
+            sys::ImGuiRsTestEngine_SetHooks(sys::igGetCurrentContext(), &mut (*recorder).hooks);
+        }
+        let mut driver = Driver {
+            harness,
+            ui_fn: Box::new(ui_fn),
+            recorder,
+            items: HashMap::new(),
+            order: Vec::new(),
+        };
+        driver.frame();
+        driver
+    }
+
+    /// Returns the harness, e.g. to render an image of the current state.
+    #[inline]
+    pub fn harness_mut(&mut self) -> &mut Harness {
+        &mut self.harness
+    }
+
+    /// Runs one frame, delivering the given input events first.
+    pub fn frame_with_events(&mut self, events: &[InputEvent]) {
+        for &event in events {
+            event.apply(self.harness.context_mut());
+        }
+        let ui_fn = &mut self.ui_fn;
+        self.harness.run_frame(|ui: &Ui| ui_fn(ui));
+
+        let (items, order) = unsafe {
+            let recorder = &mut *self.recorder;
+            (
+                std::mem::take(&mut recorder.items),
+                std::mem::take(&mut recorder.order),
+            )
+        };
+        self.items = items;
+        self.order = order;
+        let (mut active, mut hovered, mut focused) = (0, 0, 0);
+        unsafe {
+            sys::ImGuiRsTestEngine_GetItemIds(
+                sys::igGetCurrentContext(),
+                &mut active,
+                &mut hovered,
+                &mut focused,
+            );
+        }
+        for item in self.items.values_mut() {
+            item.active = active != 0 && item.id.0 == active;
+            item.hovered = hovered != 0 && item.id.0 == hovered;
+            item.focused = focused != 0 && item.id.0 == focused;
+        }
+    }
+
+    /// Runs one frame.
+    #[inline]
+    pub fn frame(&mut self) {
+        self.frame_with_events(&[]);
+    }
+
+    /// Runs `count` frames.
+    pub fn frames(&mut self, count: usize) {
+        for _ in 0..count {
+            self.frame();
+        }
+    }
+
+    /// Returns the state of the item with the given path on the last frame.
+    pub fn item(&self, path: &str) -> Result<&ItemInfo, AutomationError> {
+        self.items
+            .get(&id_from_path(path).0)
+            .ok_or_else(|| AutomationError::ItemNotFound(path.to_owned()))
+    }
+
+    /// Returns the state of the item with the given ID on the last frame.
+    pub fn item_by_id(&self, id: Id) -> Option<&ItemInfo> {
+        self.items.get(&id.0)
+    }
+
+    /// Returns the first item submitted on the last frame whose label (ignoring anything after
+    /// `##`) is `label`.
+    pub fn item_by_label(&self, label: &str) -> Result<&ItemInfo, AutomationError> {
+        self.items()
+            .find(|item| {
+                item.label
+                    .as_deref()
+                    .map(|l| l.split("##").next() == Some(label))
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| AutomationError::ItemNotFound(label.to_owned()))
+    }
+
+    /// Returns all items submitted on the last frame, in submission order.
+    pub fn items(&self) -> impl Iterator<Item = &ItemInfo> + '_ {
+        self.order.iter().filter_map(move |id| self.items.get(id))
+    }
+
+    /// Moves the mouse to a position and runs a frame.
+    pub fn mouse_move_to_pos(&mut self, pos: [f32; 2]) {
+        self.frame_with_events(&[InputEvent::MousePos(pos)]);
+    }
+
+    /// Moves the mouse to the centre of an item and runs a frame.
+    pub fn mouse_move_to(&mut self, path: &str) -> Result<(), AutomationError> {
+        let pos = self.item(path)?.center();
+        self.mouse_move_to_pos(pos);
+        Ok(())
+    }
+
+    /// Presses a mouse button and runs a frame.
+    pub fn mouse_down(&mut self, button: MouseButton) {
+        self.frame_with_events(&[InputEvent::MouseButton(button, true)]);
+    }
+
+    /// Releases a mouse button and runs a frame.
+    pub fn mouse_up(&mut self, button: MouseButton) {
+        self.frame_with_events(&[InputEvent::MouseButton(button, false)]);
+    }
+
+    /// Clicks a mouse button at the current mouse position, over two frames.
+    pub fn mouse_click(&mut self, button: MouseButton) {
+        self.mouse_down(button);
+        self.mouse_up(button);
+    }
+
+    /// Scrolls the mouse wheel and runs a frame.
+    pub fn mouse_wheel(&mut self, wheel: [f32; 2]) {
+        self.frame_with_events(&[InputEvent::MouseWheel(wheel)]);
+    }
+
+    /// Moves the mouse to an item and clicks it with the left button, over three frames.
+    pub fn click(&mut self, path: &str) -> Result<(), AutomationError> {
+        self.mouse_move_to(path)?;
+        self.mouse_click(MouseButton::Left);
+        Ok(())
+    }
+
+    /// Types text into the focused item and runs a frame.
+    pub fn type_text(&mut self, text: &str) {
+        let events: Vec<_> = text.chars().map(InputEvent::Char).collect();
+        self.frame_with_events(&events);
+    }
+
+    /// Presses a key and runs a frame.
+    pub fn key_down(&mut self, key: Key) {
+        self.frame_with_events(&[InputEvent::Key(key, true)]);
+    }
+
+    /// Releases a key and runs a frame.
+    pub fn key_up(&mut self, key: Key) {
+        self.frame_with_events(&[InputEvent::Key(key, false)]);
+    }
+
+    /// Presses and releases a key chord, e.g. `Key::S | KeyMod::CTRL`, over two frames.
+    pub fn key_press(&mut self, chord: impl Into<KeyChord>) {
+        let chord = chord.into();
+        let keys: Vec<Key> = [
+            (KeyMod::CTRL, Key::ModCtrl),
+            (KeyMod::SHIFT, Key::ModShift),
+            (KeyMod::ALT, Key::ModAlt),
+            (KeyMod::SUPER, Key::ModSuper),
+        ]
+        .iter()
+        .filter(|(m, _)| chord.mods().contains(*m))
+        .map(|&(_, key)| key)
+        .chain(chord.key())
+        .collect();
+        let down: Vec<_> = keys.iter().map(|&k| InputEvent::Key(k, true)).collect();
+        let up: Vec<_> = keys
+            .iter()
+            .rev()
+            .map(|&k| InputEvent::Key(k, false))
+            .collect();
+        self.frame_with_events(&down);
+        self.frame_with_events(&up);
+    }
+}
+
+impl Drop for Driver<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::ImGuiRsTestEngine_SetHooks(sys::igGetCurrentContext(), std::ptr::null_mut());
+            drop(Box::from_raw(self.recorder));
+        }
+    }
+}
+
+impl fmt::Debug for Driver<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Driver")
+            .field("harness", &self.harness)
+            .field("items", &self.order.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[test]
+fn test_id_from_path() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.new_frame();
+    let mut ids = Vec::new();
+    ui.window("Settings").build(|| {
+        ids.push((id_from_path("Settings/Apply"), ui.new_id_str("Apply")));
+        let _id = ui.push_id("##tabs");
+        ids.push((
+            id_from_path("Settings/##tabs/Audio###audio"),
+            ui.new_id_str("###audio"),
+        ));
+        let _id = ui.push_id_int(3);
+        ids.push((
+            id_from_path("Settings/##tabs/$$3/a\\/b"),
+            ui.new_id_str("a/b"),
+        ));
+    });
+    for (path_id, ui_id) in ids {
+        assert_eq!(path_id, ui_id);
+    }
+}
+
+#[test]
+fn test_driver_click_and_state() {
+    let mut clicks = 0;
+    let mut muted = false;
+    {
+        let mut driver = Driver::new(Harness::new([640.0, 480.0]), |ui| {
+            ui.window("Settings")
+                .position([0.0, 0.0], crate::Condition::Always)
+                .size([300.0, 200.0], crate::Condition::Always)
+                .build(|| {
+                    if ui.button("Apply") {
+                        clicks += 1;
+                    }
+                    if let Some(_tab_bar) = ui.tab_bar("##tabs") {
+                        if let Some(_tab) = ui.tab_item("General") {
+                            ui.text("General settings");
+                        }
+                        if let Some(_tab) = ui.tab_item("Audio") {
+                            ui.checkbox("Mute", &mut muted);
+                        }
+                    }
+                });
+        });
+        assert!(driver.item("Settings").is_ok());
+        let apply = driver.item("Settings/Apply").unwrap();
+        assert_eq!(apply.label(), Some("Apply"));
+        assert_eq!(
+            apply.rect_min(),
+            driver.item_by_label("Apply").unwrap().rect_min()
+        );
+        assert!(driver.item("Settings/##tabs/Audio/Mute").is_err());
+
+        driver.mouse_move_to("Settings/Apply").unwrap();
+        assert!(driver.item("Settings/Apply").unwrap().is_hovered());
+        driver.mouse_down(MouseButton::Left);
+        assert!(driver.item("Settings/Apply").unwrap().is_active());
+        driver.mouse_up(MouseButton::Left);
+        assert!(!driver.item("Settings/Apply").unwrap().is_active());
+
+        driver.click("Settings/##tabs/Audio").unwrap();
+        driver.frame();
+        let mute = driver.item("Settings/##tabs/Audio/Mute").unwrap();
+        assert_eq!(mute.is_checked(), Some(false));
+        driver.click("Settings/##tabs/Audio/Mute").unwrap();
+        let mute = driver.item("Settings/##tabs/Audio/Mute").unwrap();
+        assert_eq!(mute.is_checked(), Some(true));
+
+        assert_eq!(
+            driver.click("Settings/Missing"),
+            Err(AutomationError::ItemNotFound("Settings/Missing".into()))
+        );
+    }
+    assert_eq!(clicks, 1);
+    assert!(muted);
+}
+
+#[test]
+fn test_driver_keyboard() {
+    let mut text = String::new();
+    let mut saved = 0;
+    {
+        let mut driver = Driver::new(Harness::new([640.0, 480.0]), |ui| {
+            ui.window("Editor").build(|| {
+                ui.input_text("Name", &mut text).build();
+                if ui.shortcut_with_flags(Key::S | KeyMod::CTRL, crate::InputFlags::ROUTE_GLOBAL) {
+                    saved += 1;
+                }
+            });
+        });
+        driver.click("Editor/Name").unwrap();
+        assert!(driver.item("Editor/Name").unwrap().is_active());
+        driver.type_text("hello");
+        driver.key_press(Key::Backspace);
+        driver.key_press(Key::Enter);
+        driver.key_press(Key::S | KeyMod::CTRL);
+    }
+    assert_eq!(text, "hell");
+    assert_eq!(saved, 1);
+}
@@ -62,6 +62,11 @@ pub struct Context {
     // we also put it in an unsafecell since we're going to give
     // imgui a mutable pointer to it.
     clipboard_ctx: Box<UnsafeCell<ClipboardContext>>,
+    // settings handlers registered with Dear ImGui point into these boxes
+    #[cfg(feature = "docking")]
+    pub(crate) settings_handlers: Vec<crate::settings::RegisteredHandler>,
+    pub(crate) settings_storage: Option<Box<crate::settings::StorageState>>,
+    pub(crate) dpi_state: Option<Box<crate::dpi::DpiState>>,
 
     ui: Ui,
 }
@@ -246,6 +251,10 @@ impl Context {
             platform_name: None,
             renderer_name: None,
             clipboard_ctx: Box::new(ClipboardContext::dummy().into()),
+            #[cfg(feature = "docking")]
+            settings_handlers: Vec::new(),
+            settings_storage: None,
+            dpi_state: None,
             ui: Ui {
                 buffer: UnsafeCell::new(crate::string::UiBuffer::new(1024)),
             },
@@ -274,6 +283,10 @@ impl Context {
             platform_name: None,
             renderer_name: None,
             clipboard_ctx: Box::new(ClipboardContext::dummy().into()),
+            #[cfg(feature = "docking")]
+            settings_handlers: Vec::new(),
+            settings_storage: None,
+            dpi_state: None,
             ui: Ui {
                 buffer: UnsafeCell::new(crate::string::UiBuffer::new(1024)),
             },
@@ -327,6 +340,10 @@ impl Drop for Context {
     #[doc(alias = "DestroyContext")]
     fn drop(&mut self) {
         let _guard = CTX_MUTEX.lock();
+        if self.settings_storage.is_some() && self.is_current_context() {
+            // Like Dear ImGui does with the .ini file, save the settings one last time
+            let _ = self.save_settings();
+        }
         // If this context is the active context, Dear ImGui automatically deactivates it during
         // destruction
         unsafe {
@@ -334,6 +351,13 @@ impl Drop for Context {
             if !sys::igGetCurrentContext().is_null() && sys::igGetFrameCount() > 0 {
                 sys::igEndFrame();
             }
+            if self.shared_font_atlas.is_none() {
+                // The context owns its font atlas, which is destroyed with it
+                let current = sys::igGetCurrentContext();
+                sys::igSetCurrentContext(self.raw);
+                FontAtlas::release_owned_data((*sys::igGetIO()).Fonts);
+                sys::igSetCurrentContext(current);
+            }
             sys::igDestroyContext(self.raw);
         }
     }
@@ -379,6 +403,7 @@ impl SuspendedContext {
     /// If there is already an active context, nothing happens and `Err` is returned, containing
     /// the original suspended context.
     #[doc(alias = "SetCurrentContext")]
+    #[allow(clippy::result_large_err)]
     pub fn activate(self) -> Result<Context, SuspendedContext> {
         let _guard = CTX_MUTEX.lock();
         if no_current_context() {
@@ -401,6 +426,10 @@ impl SuspendedContext {
             platform_name: None,
             renderer_name: None,
             clipboard_ctx: Box::new(ClipboardContext::dummy().into()),
+            #[cfg(feature = "docking")]
+            settings_handlers: Vec::new(),
+            settings_storage: None,
+            dpi_state: None,
             ui: Ui {
                 buffer: UnsafeCell::new(crate::string::UiBuffer::new(1024)),
             },
@@ -609,6 +638,7 @@ impl Context {
         unsafe {
             sys::igNewFrame();
         }
+        self.update_settings_storage();
 
         &mut self.ui
     }
@@ -1,6 +1,76 @@
-use std::ptr::null;
+use bitflags::bitflags;
+use std::marker::PhantomData;
+use std::ptr;
 
-use crate::Ui;
+use crate::math::MintVec2;
+use crate::sys;
+use crate::{ConfigFlags, Direction, Id, TabItemFlags, Ui, ViewportFlags};
+
+bitflags! {
+    /// Configuration flags for dock nodes
+    #[repr(transparent)]
+    pub struct DockNodeFlags: u32 {
+        /// Don't display the dockspace node but keep it alive. Windows docked into this
+        /// dockspace node won't be undocked.
+        const KEEP_ALIVE_ONLY = sys::ImGuiDockNodeFlags_KeepAliveOnly;
+        /// Disable docking over the central node, which will be always kept empty.
+        const NO_DOCKING_OVER_CENTRAL_NODE = sys::ImGuiDockNodeFlags_NoDockingOverCentralNode;
+        /// Enable passthru dockspace: the central node is not filled with a background and
+        /// lets inputs pass through to whatever is behind it.
+        const PASSTHRU_CENTRAL_NODE = sys::ImGuiDockNodeFlags_PassthruCentralNode;
+        /// Disable other windows/nodes from splitting this node.
+        const NO_DOCKING_SPLIT = sys::ImGuiDockNodeFlags_NoDockingSplit;
+        /// Disable resizing the node using the splitter/separators.
+        const NO_RESIZE = sys::ImGuiDockNodeFlags_NoResize;
+        /// Tab bar will automatically hide when there is a single window in the dock node.
+        const AUTO_HIDE_TAB_BAR = sys::ImGuiDockNodeFlags_AutoHideTabBar;
+        /// Disable undocking this node.
+        const NO_UNDOCKING = sys::ImGuiDockNodeFlags_NoUndocking;
+        /// Disable the tab bar completely.
+        const NO_TAB_BAR = sys::ImGuiDockNodeFlags_NoTabBar as u32;
+        /// Disable the window menu button in the tab bar.
+        const NO_WINDOW_MENU_BUTTON = sys::ImGuiDockNodeFlags_NoWindowMenuButton as u32;
+        /// Disable the close button in the tab bar.
+        const NO_CLOSE_BUTTON = sys::ImGuiDockNodeFlags_NoCloseButton as u32;
+    }
+}
+
+/// Identifies a dock node: either the root node of a dockspace, or one of the
+/// nodes created by splitting it.
+///
+/// Node ids share the same id space as every other [`Id`], so the id of a
+/// dockspace is usually made with [`Ui::new_id_str`].
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default, Hash)]
+pub struct DockNodeId(pub(crate) u32);
+
+impl DockNodeId {
+    /// Creates a dock node id from a raw `ImGuiID`.
+    #[inline]
+    pub const fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    /// Returns the raw `ImGuiID` of this node.
+    #[inline]
+    pub const fn id(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<Id> for DockNodeId {
+    #[inline]
+    fn from(id: Id) -> Self {
+        DockNodeId(id.0)
+    }
+}
+
+impl From<DockNodeId> for Id {
+    #[inline]
+    fn from(id: DockNodeId) -> Self {
+        Id(id.0)
+    }
+}
 
 impl Ui {
     pub fn dockspace_over_main_viewport(&self) -> imgui_sys::ImGuiID {
@@ -9,8 +79,391 @@ impl Ui {
                 0,
                 sys::igGetMainViewport(),
                 sys::ImGuiDockNodeFlags_PassthruCentralNode as i32,
-                null(),
+                ptr::null(),
             )
         }
     }
+
+    /// Begins constructing a dockspace with the given id.
+    ///
+    /// Unlike [`Ui::dockspace_over_main_viewport`], the dockspace can be configured
+    /// with [`DockNodeFlags`], an explicit size and a [`WindowClass`].
+    /// Call [`DockSpace::build`] to submit it inside the current window, or
+    /// [`DockSpace::build_over_main_viewport`] to make it cover the main viewport.
+    #[doc(alias = "DockSpace")]
+    pub fn dock_space(&self, id: impl Into<DockNodeId>) -> DockSpace<'_> {
+        DockSpace::new(self, id.into())
+    }
+
+    /// Returns true if a dock node with the given id currently exists.
+    ///
+    /// Dock nodes are restored from the .ini settings, so this can be used to only
+    /// build a default layout with [`Ui::dock_builder`] on first launch.
+    #[doc(alias = "DockBuilderGetNode")]
+    pub fn dock_node_exists(&self, node: impl Into<DockNodeId>) -> bool {
+        unsafe { !sys::igDockBuilderGetNode(node.into().0).is_null() }
+    }
+
+    /// Starts building a dock layout programmatically.
+    ///
+    /// Any existing node with the given id is removed, along with its children,
+    /// and replaced by an empty dockspace node covering the main viewport.
+    /// Split it with [`DockBuilder::split_node`], assign windows to the resulting nodes
+    /// with [`DockBuilder::dock_window`], and then call [`DockBuilder::finish`].
+    ///
+    /// This must be called before the dockspace itself is submitted in the frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`ConfigFlags::DOCKING_ENABLE`] is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// fn layout(ui: &Ui) {
+    ///     let dockspace = DockNodeId::from(ui.new_id_str("main_dockspace"));
+    ///     if !ui.dock_node_exists(dockspace) {
+    ///         let builder = ui.dock_builder(dockspace);
+    ///         let (left, rest) = builder.split_node(builder.root(), Direction::Left, 0.25);
+    ///         let (bottom, center) = builder.split_node(rest, Direction::Down, 0.3);
+    ///         builder.dock_window("Outliner", left);
+    ///         builder.dock_window("Console", bottom);
+    ///         builder.dock_window("Viewport", center);
+    ///         builder.finish();
+    ///     }
+    /// }
+    /// ```
+    #[doc(alias = "DockBuilderAddNode", alias = "DockBuilderRemoveNode")]
+    pub fn dock_builder(&self, dockspace_id: impl Into<DockNodeId>) -> DockBuilder<'_> {
+        DockBuilder::new(self, dockspace_id.into())
+    }
+}
+
+/// Builder for a dockspace, created with [`Ui::dock_space`].
+#[derive(Debug)]
+#[must_use]
+pub struct DockSpace<'ui> {
+    _phantom: PhantomData<&'ui Ui>,
+    id: DockNodeId,
+    size: MintVec2,
+    flags: DockNodeFlags,
+    window_class: Option<WindowClass>,
+}
+
+impl<'ui> DockSpace<'ui> {
+    fn new(_: &'ui Ui, id: DockNodeId) -> Self {
+        DockSpace {
+            _phantom: PhantomData,
+            id,
+            size: [0.0, 0.0].into(),
+            flags: DockNodeFlags::empty(),
+            window_class: None,
+        }
+    }
+
+    /// Sets the size of the dockspace.
+    ///
+    /// A value of 0.0 on an axis uses the remaining available space on that axis.
+    #[inline]
+    pub fn size(mut self, size: impl Into<MintVec2>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// Replace current dock node flags with the given value
+    #[inline]
+    pub fn flags(mut self, flags: DockNodeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Restricts the dockspace to windows of the given class.
+    ///
+    /// Only windows submitted with a matching [`Window::window_class`](crate::Window::window_class)
+    /// can be docked into it, unless the class allows unclassed windows.
+    #[inline]
+    pub fn window_class(mut self, window_class: WindowClass) -> Self {
+        self.window_class = Some(window_class);
+        self
+    }
+
+    fn window_class_ptr(&self) -> *const sys::ImGuiWindowClass {
+        self.window_class
+            .as_ref()
+            .map(|class| class as *const WindowClass as *const sys::ImGuiWindowClass)
+            .unwrap_or(ptr::null())
+    }
+
+    /// Submits the dockspace inside the current window, and returns its id.
+    pub fn build(self) -> DockNodeId {
+        let id = unsafe {
+            sys::igDockSpace(
+                self.id.0,
+                self.size.into(),
+                self.flags.bits() as i32,
+                self.window_class_ptr(),
+            )
+        };
+        DockNodeId(id)
+    }
+
+    /// Submits the dockspace in its own window covering the main viewport, and returns
+    /// its id.
+    ///
+    /// The size set with [`DockSpace::size`] is ignored.
+    #[doc(alias = "DockSpaceOverViewport")]
+    pub fn build_over_main_viewport(self) -> DockNodeId {
+        let id = unsafe {
+            sys::igDockSpaceOverViewport(
+                self.id.0,
+                sys::igGetMainViewport(),
+                self.flags.bits() as i32,
+                self.window_class_ptr(),
+            )
+        };
+        DockNodeId(id)
+    }
+}
+
+/// Describes a class of windows, used to restrict which windows can be docked together
+/// and to override some of their behaviour.
+///
+/// Pass it to [`Window::window_class`](crate::Window::window_class) and
+/// [`DockSpace::window_class`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WindowClass {
+    /// Class id. Windows with different non-zero class ids cannot be docked with
+    /// each other.
+    pub class_id: Id,
+    /// Hint for the platform backend. Defaults to an invalid id, which lets the
+    /// platform backend decide.
+    pub parent_viewport_id: Id,
+    /// Id of the parent window for shortcut focus route evaluation.
+    pub focus_route_parent_window_id: Id,
+    /// Viewport flags to set when a window of this class owns a viewport.
+    pub viewport_flags_override_set: ViewportFlags,
+    /// Viewport flags to clear when a window of this class owns a viewport.
+    pub viewport_flags_override_clear: ViewportFlags,
+    /// Tab item flags to set when a window of this class gets submitted into a dock node
+    /// tab bar.
+    pub tab_item_flags_override_set: TabItemFlags,
+    /// Dock node flags to set when a window of this class is hosted by a dock node.
+    pub dock_node_flags_override_set: DockNodeFlags,
+    /// Set to true to enforce a single window of this class always having their own tab bar.
+    pub docking_always_tab_bar: bool,
+    /// Set to true to allow windows of this class to be docked/merged with an unclassed window.
+    pub docking_allow_unclassed: bool,
+}
+
+impl WindowClass {
+    /// Creates a window class with the given class id and default settings.
+    pub fn new(class_id: impl Into<Id>) -> Self {
+        WindowClass {
+            class_id: class_id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for WindowClass {
+    fn default() -> Self {
+        WindowClass {
+            class_id: Id(0),
+            parent_viewport_id: Id(u32::MAX),
+            focus_route_parent_window_id: Id(0),
+            viewport_flags_override_set: ViewportFlags::empty(),
+            viewport_flags_override_clear: ViewportFlags::empty(),
+            tab_item_flags_override_set: TabItemFlags::empty(),
+            dock_node_flags_override_set: DockNodeFlags::empty(),
+            docking_always_tab_bar: false,
+            docking_allow_unclassed: true,
+        }
+    }
+}
+
+/// Builder for a dock layout, created with [`Ui::dock_builder`].
+///
+/// The layout is only applied once [`DockBuilder::finish`] is called.
+#[derive(Debug)]
+#[must_use]
+pub struct DockBuilder<'ui> {
+    ui: &'ui Ui,
+    root: DockNodeId,
+}
+
+impl<'ui> DockBuilder<'ui> {
+    fn new(ui: &'ui Ui, root: DockNodeId) -> Self {
+        assert!(
+            ui.io().config_flags.contains(ConfigFlags::DOCKING_ENABLE),
+            "docking must be enabled to build a dock layout"
+        );
+        unsafe {
+            sys::igDockBuilderRemoveNode(root.0);
+            sys::igDockBuilderAddNode(root.0, sys::ImGuiDockNodeFlags_DockSpace);
+            let viewport = sys::igGetMainViewport();
+            sys::igDockBuilderSetNodeSize(root.0, (*viewport).Size);
+        }
+        DockBuilder { ui, root }
+    }
+
+    /// Returns the id of the dockspace node this builder was created for.
+    #[inline]
+    pub fn root(&self) -> DockNodeId {
+        self.root
+    }
+
+    /// Sets the position of a node, in screen coordinates.
+    #[doc(alias = "DockBuilderSetNodePos")]
+    pub fn set_node_pos(&self, node: DockNodeId, pos: impl Into<MintVec2>) {
+        unsafe { sys::igDockBuilderSetNodePos(node.0, pos.into().into()) }
+    }
+
+    /// Sets the size of a node.
+    ///
+    /// The root node defaults to the size of the main viewport. Split ratios are
+    /// applied to the current size of a node, so set the size before splitting it.
+    #[doc(alias = "DockBuilderSetNodeSize")]
+    pub fn set_node_size(&self, node: DockNodeId, size: impl Into<MintVec2>) {
+        unsafe { sys::igDockBuilderSetNodeSize(node.0, size.into().into()) }
+    }
+
+    /// Splits a node in two.
+    ///
+    /// `ratio` is the fraction of the node given to the new node on the `dir` side.
+    /// Returns `(node_at_dir, node_at_opposite_dir)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dir` is [`Direction::None`].
+    #[doc(alias = "DockBuilderSplitNode")]
+    pub fn split_node(
+        &self,
+        node: DockNodeId,
+        dir: Direction,
+        ratio: f32,
+    ) -> (DockNodeId, DockNodeId) {
+        assert!(
+            dir != Direction::None,
+            "cannot split a dock node without a direction"
+        );
+
+        let mut at_dir = 0;
+        let mut at_opposite_dir = 0;
+        unsafe {
+            sys::igDockBuilderSplitNode(
+                node.0,
+                dir as i32,
+                ratio,
+                &mut at_dir,
+                &mut at_opposite_dir,
+            );
+        }
+        (DockNodeId(at_dir), DockNodeId(at_opposite_dir))
+    }
+
+    /// Docks the window with the given name into a node.
+    ///
+    /// The window does not need to exist yet; it will be docked the first time it is submitted.
+    #[doc(alias = "DockBuilderDockWindow")]
+    pub fn dock_window(&self, window_name: impl AsRef<str>, node: DockNodeId) {
+        unsafe { sys::igDockBuilderDockWindow(self.ui.scratch_txt(window_name), node.0) }
+    }
+
+    /// Finishes the layout and applies it.
+    #[doc(alias = "DockBuilderFinish")]
+    pub fn finish(self) {
+        unsafe { sys::igDockBuilderFinish(self.root.0) }
+    }
+}
+
+#[test]
+fn test_dock_builder_split() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_flags |= ConfigFlags::DOCKING_ENABLE;
+    let ui = ctx.new_frame();
+
+    let dockspace = DockNodeId::from(ui.new_id_str("dockspace"));
+    assert!(!ui.dock_node_exists(dockspace));
+
+    let builder = ui.dock_builder(dockspace);
+    assert_eq!(builder.root(), dockspace);
+    let (left, right) = builder.split_node(builder.root(), Direction::Left, 0.25);
+    builder.dock_window("Left", left);
+    builder.dock_window("Right", right);
+    builder.finish();
+
+    assert!(ui.dock_node_exists(dockspace));
+    assert!(ui.dock_node_exists(left));
+    assert!(ui.dock_node_exists(right));
+    assert_ne!(left, right);
+}
+
+#[test]
+fn test_window_class_memory_layout() {
+    use std::mem;
+    assert_eq!(
+        mem::size_of::<WindowClass>(),
+        mem::size_of::<sys::ImGuiWindowClass>()
+    );
+    assert_eq!(
+        mem::align_of::<WindowClass>(),
+        mem::align_of::<sys::ImGuiWindowClass>()
+    );
+    use sys::ImGuiWindowClass;
+    macro_rules! assert_field_offset {
+        ($l:ident, $r:ident) => {
+            assert_eq!(
+                memoffset::offset_of!(WindowClass, $l),
+                memoffset::offset_of!(ImGuiWindowClass, $r)
+            );
+        };
+    }
+
+    assert_field_offset!(class_id, ClassId);
+    assert_field_offset!(parent_viewport_id, ParentViewportId);
+    assert_field_offset!(focus_route_parent_window_id, FocusRouteParentWindowId);
+    assert_field_offset!(viewport_flags_override_set, ViewportFlagsOverrideSet);
+    assert_field_offset!(viewport_flags_override_clear, ViewportFlagsOverrideClear);
+    assert_field_offset!(tab_item_flags_override_set, TabItemFlagsOverrideSet);
+    assert_field_offset!(dock_node_flags_override_set, DockNodeFlagsOverrideSet);
+    assert_field_offset!(docking_always_tab_bar, DockingAlwaysTabBar);
+    assert_field_offset!(docking_allow_unclassed, DockingAllowUnclassed);
+}
+
+#[test]
+fn test_window_class_default() {
+    let class = WindowClass::default();
+    let raw = unsafe {
+        let raw = sys::ImGuiWindowClass_ImGuiWindowClass();
+        let copy = *raw;
+        sys::ImGuiWindowClass_destroy(raw);
+        copy
+    };
+    assert_eq!(class.parent_viewport_id.0, raw.ParentViewportId);
+    assert_eq!(class.docking_allow_unclassed, raw.DockingAllowUnclassed);
+    assert_eq!(class.docking_always_tab_bar, raw.DockingAlwaysTabBar);
+}
+
+#[test]
+fn test_dock_space_window_class() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_flags |= ConfigFlags::DOCKING_ENABLE;
+    let ui = ctx.new_frame();
+
+    let class = WindowClass::new(ui.new_id_str("tools"));
+    let dockspace = ui
+        .dock_space(ui.new_id_str("dockspace"))
+        .flags(DockNodeFlags::NO_DOCKING_SPLIT | DockNodeFlags::AUTO_HIDE_TAB_BAR)
+        .window_class(class)
+        .build_over_main_viewport();
+    assert!(ui.dock_node_exists(dockspace));
+
+    ui.window("Tool")
+        .dock_id(dockspace, crate::Condition::Always)
+        .window_class(class)
+        .build(|| {
+            ui.text("docked");
+        });
 }
@@ -0,0 +1,186 @@
+use crate::{Context, Style};
+
+/// Unscaled values that [`Context::set_dpi_scale`] scales from.
+#[derive(Debug)]
+pub(crate) struct DpiState {
+    scale: f32,
+    base_style: Style,
+    base_font_global_scale: f32,
+}
+
+/// # DPI scaling
+impl Context {
+    /// Scales the user interface for a display with the given DPI scale, e.g. `2.0` for a
+    /// 200% monitor.
+    ///
+    /// Style sizes and [`Io::font_global_scale`](crate::Io::font_global_scale) are computed
+    /// from the [unscaled style](Self::set_unscaled_style), so repeated calls don't compound.
+    /// The font atlas is rasterized at the new density to keep text sharp, including fonts
+    /// added later; see
+    /// [`FontAtlas::set_rasterizer_density`](crate::FontAtlas::set_rasterizer_density).
+    ///
+    /// Returns `true` if the scale changed. The font texture is then invalid and has to be
+    /// rebuilt and uploaded again by the renderer before the next frame:
+    ///
+    /// ```no_run
+    /// # let mut ctx = imgui::Context::create();
+    /// # let mut renderer = imgui::render::software::SoftwareRenderer::new(&mut ctx);
+    /// # let monitor_dpi_scale = 2.0;
+    /// if ctx.set_dpi_scale(monitor_dpi_scale) {
+    ///     renderer.reload_font_texture(ctx.fonts());
+    /// }
+    /// ```
+    ///
+    /// The first call remembers the current style and font scale as unscaled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is not positive, or if called between [`new_frame`](Self::new_frame)
+    /// and [`render`](Self::render).
+    pub fn set_dpi_scale(&mut self, scale: f32) -> bool {
+        assert!(scale > 0.0, "DPI scale must be positive");
+        assert!(
+            !self.fonts().is_locked(),
+            "cannot change the DPI scale between new_frame and render"
+        );
+        if self.dpi_state.is_none() {
+            self.dpi_state = Some(Box::new(DpiState {
+                scale: 1.0,
+                base_style: *self.style(),
+                base_font_global_scale: self.io().font_global_scale,
+            }));
+        }
+        let state = self.dpi_state.as_mut().unwrap();
+        if state.scale == scale {
+            return false;
+        }
+        state.scale = scale;
+        self.apply_dpi_scale();
+        self.fonts().set_rasterizer_density(scale);
+        true
+    }
+
+    /// Returns the scale last set with [`set_dpi_scale`](Self::set_dpi_scale), or `1.0`.
+    pub fn dpi_scale(&self) -> f32 {
+        self.dpi_state.as_ref().map_or(1.0, |state| state.scale)
+    }
+
+    /// Replaces the unscaled style and applies it at the current DPI scale.
+    ///
+    /// Use this instead of [`style_mut`](Self::style_mut) for theme changes once
+    /// [`set_dpi_scale`](Self::set_dpi_scale) is in use, since the next scale change
+    /// otherwise reverts them.
+    pub fn set_unscaled_style(&mut self, style: Style) {
+        match &mut self.dpi_state {
+            Some(state) => {
+                state.base_style = style;
+                self.apply_dpi_scale();
+            }
+            None => *self.style_mut() = style,
+        }
+    }
+
+    /// Returns the style before DPI scaling.
+    pub fn unscaled_style(&self) -> Style {
+        self.dpi_state
+            .as_ref()
+            .map_or(*self.style(), |state| state.base_style)
+    }
+
+    fn apply_dpi_scale(&mut self) {
+        let state = self.dpi_state.as_ref().unwrap();
+        let mut style = state.base_style;
+        style.scale_all_sizes(state.scale);
+        let font_global_scale = state.base_font_global_scale * state.scale;
+        *self.style_mut() = style;
+        self.io_mut().font_global_scale = font_global_scale;
+    }
+}
+
+#[test]
+fn test_dpi_scale_does_not_compound() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let base = *ctx.style();
+    let [width, height] = {
+        let texture = ctx.fonts().build_rgba32_texture();
+        [texture.width, texture.height]
+    };
+
+    assert_eq!(ctx.dpi_scale(), 1.0);
+    assert!(!ctx.set_dpi_scale(1.0));
+    assert!(ctx.set_dpi_scale(2.0));
+    let doubled = *ctx.style();
+    assert_eq!(doubled.window_padding, [16.0, 16.0]);
+    assert_eq!(doubled.item_spacing, [16.0, 8.0]);
+    assert_eq!(ctx.io().font_global_scale, 2.0);
+    assert!(!ctx.set_dpi_scale(2.0));
+
+    // Glyphs are rasterized at twice the density
+    let texture = ctx.fonts().build_rgba32_texture();
+    assert!(texture.width * texture.height > width * height);
+
+    assert!(ctx.set_dpi_scale(1.25));
+    assert!(ctx.set_dpi_scale(2.0));
+    assert_eq!(*ctx.style(), doubled);
+    assert!(ctx.set_dpi_scale(1.0));
+    assert_eq!(*ctx.style(), base);
+    assert_eq!(ctx.io().font_global_scale, 1.0);
+    assert_eq!(ctx.unscaled_style(), base);
+
+    ctx.new_frame().text("scaled");
+    ctx.render();
+}
+
+#[test]
+fn test_dpi_scale_applies_to_new_fonts() {
+    use crate::FontSource;
+
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let glyph_width = |ctx: &mut Context, font| {
+        let texture_width = ctx.fonts().build_rgba32_texture().width as f32;
+        let glyph = ctx.fonts().get_font(font).unwrap().get_glyph('M');
+        (glyph.u1 - glyph.u0) * texture_width
+    };
+    let font = ctx
+        .fonts()
+        .add_font(&[FontSource::DefaultFontData { config: None }]);
+    let unscaled = glyph_width(&mut ctx, font);
+
+    ctx.set_dpi_scale(2.0);
+    let scaled = glyph_width(&mut ctx, font);
+    assert!(scaled > unscaled);
+    let added = ctx
+        .fonts()
+        .add_font(&[FontSource::DefaultFontData { config: None }]);
+    assert_eq!(glyph_width(&mut ctx, added), scaled);
+
+    // The density outlives the fonts, e.g. when they are reloaded
+    ctx.fonts().clear();
+    let reloaded = ctx
+        .fonts()
+        .add_font(&[FontSource::DefaultFontData { config: None }]);
+    assert_eq!(glyph_width(&mut ctx, reloaded), scaled);
+}
+
+#[test]
+fn test_dpi_unscaled_style() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.set_dpi_scale(2.0);
+
+    let mut style = ctx.unscaled_style();
+    style.frame_rounding = 3.0;
+    ctx.set_unscaled_style(style);
+    assert_eq!(ctx.style().frame_rounding, 6.0);
+
+    ctx.set_dpi_scale(3.0);
+    assert_eq!(ctx.style().frame_rounding, 9.0);
+    assert_eq!(ctx.unscaled_style(), style);
+}
+
+#[test]
+#[should_panic(expected = "between new_frame and render")]
+fn test_dpi_scale_during_frame() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.new_frame();
+    ctx.set_dpi_scale(2.0);
+}
@@ -14,21 +14,27 @@
 //!
 //! 1.  Users can give an [empty payload](DragDropPayloadEmpty) with [begin](DragDropSource::begin).
 //!     This payload type is essentially just a notification system, but using some shared state,
-//!     this can be reasonably powerful, and is the safest way to transfer non-Copy data offered
-//!     right now.
+//!     this can be reasonably powerful.
 //! 2.  Users can give a [simple Copy payload](DragDropPayloadPod) with [begin](DragDropSource::begin_payload).
 //!     This allows users to copy data to Dear ImGui, which will take ownership over it, and then be given
 //!     it back to the Target. Please note: users are of course free to not drop any drag (cancel a drag),
 //!     so this data could easily be lost forever. Our `'static + Copy` bound is intended to keep users
 //!     to simplistic types.
-//! 3.  An unsafe implementation is provided which allows for any data to be unsafely copied. Note that once
-//!     you use this method, the safe implementations in #1 and #2 can create memory unsafety problems; notably,
-//!     they both assume that a payload has certain header information within it.
+//! 3.  Users can give an [owned payload](DragDropPayloadOwned) of any `'static` type, such as a `String` or
+//!     a `Vec`, with [begin_payload_with](DragDropSource::begin_payload_with). The value stays on the Rust
+//!     side, and only a token referring to it is given to Dear ImGui. It is moved to the target on delivery.
+//! 4.  An unsafe implementation is provided which allows for any data to be unsafely copied. Note that once
+//!     you use this method, the safe implementations in #1, #2 and #3 can create memory unsafety problems;
+//!     notably, they all assume that a payload has certain header information within it.
 //!
 //! For examples of each payload type, see [DragDropSource].
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::{any, ffi, marker::PhantomData};
 
-use crate::{sys, Condition, Ui};
+#[cfg(feature = "docking")]
+use crate::math::MintVec2;
+use crate::{sys, Condition, Id, Ui};
 use bitflags::bitflags;
 
 bitflags!(
@@ -80,6 +86,54 @@ impl Ui {
             ui: self,
         }
     }
+
+    /// Begins or continues a drag that comes from outside of Dear ImGui, such as files dragged
+    /// over the application window by the operating system.
+    ///
+    /// Platform backends call this every frame while an external drag hovers the window, before
+    /// any widget is submitted, and report the drag position as the mouse position. Widgets accept
+    /// the payload like any owned payload, with
+    /// [accept_payload_owned](DragDropTarget::accept_payload_owned). `make_payload` is only
+    /// called on the first frame of the drag.
+    ///
+    /// When the external drag is dropped, the backend stops calling this function, and the
+    /// payload is delivered to the target under the mouse during the next frame. When the drag
+    /// leaves the window instead, the backend reports the mouse as unavailable before it stops,
+    /// so that no target is hovered.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # use std::path::PathBuf;
+    /// fn show_ui(ui: &Ui, hovering_files: Option<&Vec<PathBuf>>, opened: &mut Vec<PathBuf>) {
+    ///     if let Some(files) = hovering_files {
+    ///         ui.begin_external_drag("FILES", || files.clone()).end();
+    ///     }
+    ///
+    ///     ui.window("Project").build(|| {
+    ///         ui.button("Drop files here");
+    ///         if let Some(target) = ui.drag_drop_target() {
+    ///             if let Some(Ok(payload)) = target
+    ///                 .accept_payload_owned::<Vec<PathBuf>, _>("FILES", DragDropFlags::empty())
+    ///             {
+    ///                 opened.extend(payload.data.unwrap_or_default());
+    ///             }
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// The returned tooltip works like the one of [DragDropSource::begin].
+    #[doc(alias = "BeginDragDropSource")]
+    pub fn begin_external_drag<P: 'static>(
+        &self,
+        name: impl AsRef<str>,
+        make_payload: impl FnOnce() -> P,
+    ) -> DragDropSourceToolTip<'_> {
+        self.drag_drop_source_config(name)
+            .flags(DragDropFlags::SOURCE_EXTERN)
+            .begin_payload_with(make_payload)
+            .expect("external drag and drop sources always begin")
+    }
 }
 
 /// Creates a source for drag drop data out of the last ID created.
@@ -99,7 +153,7 @@ impl Ui {
 /// names must match up. A single item should only have one [DragDropSource], though
 /// a target may have multiple different targets.
 ///
-/// DropDropSources don't do anything until you use one of the three `begin_` methods
+/// DropDropSources don't do anything until you use one of the `begin_` methods
 /// on this struct. Each of these methods describes how you handle the Payload which ImGui
 /// will manage, and then give to a [DragDropTarget], which will received the payload. The
 /// simplest and safest Payload is the empty payload, created with [begin](Self::begin).
@@ -248,6 +302,70 @@ impl<'ui, T: AsRef<str>> DragDropSource<'ui, T> {
         }
     }
 
+    /// Creates the source of a drag with an owned payload and returns a handle on the tooltip.
+    /// The tooltip works like the one returned by [begin](Self::begin).
+    ///
+    /// Unlike [begin_payload](Self::begin_payload), the payload can be any `'static` type,
+    /// such as a `String` or a `Vec`. `make_payload` is called once when the drag starts, and the
+    /// value is kept on the Rust side until a target accepts it with
+    /// [accept_payload_owned](DragDropTarget::accept_payload_owned), which moves it out. A
+    /// cancelled drag leaves its value in place until the next drag of the same type.
+    ///
+    /// The value is kept per thread rather than per context, so it can also be accepted in
+    /// another context on the same thread.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// fn show_ui(ui: &Ui, selection: &[u32], trash: &mut Vec<u32>) {
+    ///     ui.button("Drag the selection!");
+    ///
+    ///     // drag drop SOURCE
+    ///     if let Some(tooltip) = ui
+    ///         .drag_drop_source_config("ASSETS")
+    ///         .begin_payload_with(|| selection.to_vec())
+    ///     {
+    ///         ui.text(format!("{} assets", selection.len()));
+    ///         tooltip.end();
+    ///     }
+    ///
+    ///     ui.button("Trash");
+    ///
+    ///     // drag drop TARGET
+    ///     if let Some(target) = ui.drag_drop_target() {
+    ///         if let Some(Ok(payload)) =
+    ///             target.accept_payload_owned::<Vec<u32>, _>("ASSETS", DragDropFlags::empty())
+    ///         {
+    ///             trash.extend(payload.data.unwrap_or_default());
+    ///         }
+    ///
+    ///         target.pop();
+    ///     }
+    /// }
+    /// ```
+    pub fn begin_payload_with<P: 'static>(
+        self,
+        make_payload: impl FnOnce() -> P,
+    ) -> Option<DragDropSourceToolTip<'ui>> {
+        unsafe {
+            if !sys::igBeginDragDropSource(self.flags.bits() as i32) {
+                return None;
+            }
+            let token = match current_owned_payload_token::<P>(self.ui.scratch_txt(&self.name)) {
+                // The drag already stored its value in an earlier frame
+                Some(token) => token,
+                None => store_owned_payload(make_payload()),
+            };
+            let payload = TypedPayload::new(OwnedPayloadToken::<P>::new(token));
+            sys::igSetDragDropPayload(
+                self.ui.scratch_txt(&self.name),
+                &payload as *const _ as *const ffi::c_void,
+                std::mem::size_of_val(&payload),
+                self.cond as i32,
+            );
+            Some(DragDropSourceToolTip::push())
+        }
+    }
+
     /// Creates the source of a drag and returns a handle on the tooltip.
     /// This handle can be immediately dropped without binding it, in which case a default empty
     /// circle will be used for the "blank" tooltip as this item is being dragged around.
@@ -328,6 +446,107 @@ impl Ui {
             None
         }
     }
+
+    /// Creates a new DragDropTarget on a rectangle in screen coordinates instead of the last
+    /// item, such as a node graph socket or a timeline track drawn with the draw list API.
+    ///
+    /// `id` identifies the target; it must not be zero, and should stay the same from frame to
+    /// frame. Targets can overlap, in which case the smallest one under the mouse receives the
+    /// payload.
+    ///
+    /// Requires the `docking` feature, whose bindings include this part of the internal API.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is zero.
+    #[cfg(feature = "docking")]
+    #[doc(alias = "BeginDragDropTargetCustom")]
+    pub fn drag_drop_target_rect(
+        &self,
+        min: impl Into<MintVec2>,
+        max: impl Into<MintVec2>,
+        id: Id,
+    ) -> Option<DragDropTarget<'_>> {
+        assert_ne!(id.0, 0, "A drag and drop target needs a non-zero id");
+        let rect = sys::ImRect {
+            Min: min.into().into(),
+            Max: max.into().into(),
+        };
+        let should_begin = unsafe { sys::igBeginDragDropTargetCustom(rect, id.0) };
+        if should_begin {
+            Some(DragDropTarget(self))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the payload of the drag and drop in progress, if any.
+    ///
+    /// Unlike a [DragDropTarget], this works anywhere, e.g. to highlight the places a payload
+    /// can be dropped on while it is being dragged:
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// fn show_ui(ui: &Ui) {
+    ///     let dragging_asset = ui
+    ///         .drag_drop_payload()
+    ///         .is_some_and(|payload| payload.is_data_type("ASSET") && payload.holds::<u32>());
+    ///     let color = if dragging_asset { [0.2, 0.8, 0.2, 1.0] } else { [0.5, 0.5, 0.5, 1.0] };
+    ///     let _color = ui.push_style_color(StyleColor::Button, color);
+    ///     ui.button("Asset slot");
+    /// }
+    /// ```
+    #[doc(alias = "GetDragDropPayload")]
+    pub fn drag_drop_payload(&self) -> Option<DragDropPayloadInfo<'_>> {
+        let raw = unsafe { sys::igGetDragDropPayload() };
+        if raw.is_null() {
+            None
+        } else {
+            Some(DragDropPayloadInfo { raw, ui: self })
+        }
+    }
+}
+
+/// The payload of the drag and drop in progress, see [Ui::drag_drop_payload].
+#[derive(Debug)]
+pub struct DragDropPayloadInfo<'ui> {
+    raw: *const sys::ImGuiPayload,
+    ui: &'ui Ui,
+}
+
+impl DragDropPayloadInfo<'_> {
+    /// Returns `true` if the payload was sent with the given name.
+    #[doc(alias = "IsDataType")]
+    pub fn is_data_type(&self, name: impl AsRef<str>) -> bool {
+        unsafe { sys::ImGuiPayload_IsDataType(self.raw as *mut _, self.ui.scratch_txt(name)) }
+    }
+
+    /// Returns `true` if the payload was sent with [DragDropSource::begin_payload] and
+    /// contains a `T`.
+    pub fn holds<T: 'static + Copy>(&self) -> bool {
+        unsafe { typed_payload_data::<T>(&*self.raw).is_some() }
+    }
+
+    /// Returns `true` if the payload was sent with [DragDropSource::begin_payload_with] and
+    /// contains a `P`.
+    pub fn holds_owned<P: 'static>(&self) -> bool {
+        self.holds::<OwnedPayloadToken<P>>()
+    }
+
+    /// Returns the id of the source of the drag.
+    pub fn source_id(&self) -> Id {
+        Id(unsafe { (*self.raw).SourceId })
+    }
+
+    /// Set when the payload is being dragged over a target which accepts it.
+    pub fn preview(&self) -> bool {
+        unsafe { (*self.raw).Preview }
+    }
+
+    /// Set when the payload has been dropped on a target which accepts it.
+    pub fn delivery(&self) -> bool {
+        unsafe { (*self.raw).Delivery }
+    }
 }
 
 /// Creates a target for drag drop data out of the last ID created.
@@ -430,6 +649,29 @@ impl<'ui> DragDropTarget<'ui> {
         })
     }
 
+    /// Accepts an owned payload sent with [DragDropSource::begin_payload_with]. The sent type
+    /// must match `P` to receive an `Ok`.
+    ///
+    /// The value is moved out of the drag on delivery, so `data` is only `Some` when
+    /// `delivery` is set. Before that, e.g. with `ACCEPT_BEFORE_DELIVERY`, the payload only
+    /// tells that a value of type `P` is being dragged over the target.
+    pub fn accept_payload_owned<P: 'static, Name: AsRef<str>>(
+        &self,
+        name: Name,
+        flags: DragDropFlags,
+    ) -> Option<Result<DragDropPayloadOwned<P>, PayloadIsWrongType>> {
+        let output = self.accept_payload::<OwnedPayloadToken<P>, _>(name, flags)?;
+        Some(output.map(|payload| DragDropPayloadOwned {
+            data: if payload.delivery {
+                take_owned_payload::<P>(payload.data.token)
+            } else {
+                None
+            },
+            preview: payload.preview,
+            delivery: payload.delivery,
+        }))
+    }
+
     /// Accepts a drag and drop payload  which contains a raw pointer to [c_void](std::ffi::c_void)
     /// and a size in bytes. Users should generally avoid using this function
     /// if one of the safer variants is acceptable.
@@ -518,6 +760,25 @@ pub struct DragDropPayloadPod<T: 'static + Copy> {
     pub delivery: bool,
 }
 
+/// A DragDropPayload with status information and an owned value in it, see
+/// [`accept_payload_owned`](DragDropTarget::accept_payload_owned).
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DragDropPayloadOwned<P> {
+    /// The value sent by the source, moved out of the drag on delivery.
+    ///
+    /// `None` before delivery, and if another target already took the value.
+    pub data: Option<P>,
+
+    /// Set when [`accept_payload_owned`](DragDropTarget::accept_payload_owned) was called
+    /// and mouse has been hovering the target item.
+    pub preview: bool,
+
+    /// Set when [`accept_payload_owned`](DragDropTarget::accept_payload_owned) was
+    /// called and mouse button is released over the target item.
+    pub delivery: bool,
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct DragDropPayload {
@@ -583,6 +844,113 @@ impl TypedPayloadHeader {
     }
 }
 
+/// The payload of an owned drag, referring to a value in [`OWNED_PAYLOADS`].
+struct OwnedPayloadToken<P> {
+    token: u64,
+    _payload: PhantomData<fn() -> P>,
+}
+
+impl<P> OwnedPayloadToken<P> {
+    fn new(token: u64) -> Self {
+        Self {
+            token,
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<P> Clone for OwnedPayloadToken<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for OwnedPayloadToken<P> {}
+
+/// The value of an owned drag.
+struct OwnedPayload {
+    token: u64,
+    value: Box<dyn any::Any>,
+}
+
+thread_local! {
+    /// Values of owned drags, keyed by their type.
+    static OWNED_PAYLOADS: RefCell<HashMap<any::TypeId, OwnedPayload>> =
+        RefCell::new(HashMap::new());
+    static NEXT_OWNED_PAYLOAD_TOKEN: Cell<u64> = const { Cell::new(1) };
+}
+
+/// Stores the value of a new drag, replacing the value of an earlier one of the same type.
+fn store_owned_payload<P: 'static>(value: P) -> u64 {
+    let token = NEXT_OWNED_PAYLOAD_TOKEN.with(|next| next.replace(next.get() + 1));
+    OWNED_PAYLOADS.with(|payloads| {
+        payloads.borrow_mut().insert(
+            any::TypeId::of::<P>(),
+            OwnedPayload {
+                token,
+                value: Box::new(value),
+            },
+        )
+    });
+    token
+}
+
+/// Takes the value of the drag with the given token, if it is still stored.
+fn take_owned_payload<P: 'static>(token: u64) -> Option<P> {
+    OWNED_PAYLOADS.with(|payloads| {
+        let mut payloads = payloads.borrow_mut();
+        match payloads.get(&any::TypeId::of::<P>()) {
+            Some(stored) if stored.token == token => {
+                let stored = payloads.remove(&any::TypeId::of::<P>())?;
+                stored.value.downcast().ok().map(|value| *value)
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Returns the data of a payload sent as a `TypedPayload<T>`, or `None` if it holds another
+/// type.
+///
+/// # Safety
+///
+/// `payload` must be the active payload, so that its data is valid.
+unsafe fn typed_payload_data<T: 'static + Copy>(payload: &sys::ImGuiPayload) -> Option<T> {
+    if payload.Data.is_null() || payload.DataSize as usize != std::mem::size_of::<TypedPayload<T>>()
+    {
+        return None;
+    }
+    // Only read the type id, the data may not be a header at all
+    let data = payload.Data as *const TypedPayload<T>;
+    let type_id = std::ptr::addr_of!((*data).header.type_id).read_unaligned();
+    if type_id == any::TypeId::of::<T>() {
+        Some(std::ptr::addr_of!((*data).data).read_unaligned())
+    } else {
+        None
+    }
+}
+
+/// Returns the token of the active drag, if it is an owned drag of `P` named `name` whose value
+/// is still stored.
+///
+/// # Safety
+///
+/// Must be called while a drag and drop source is active.
+unsafe fn current_owned_payload_token<P: 'static>(name: *const ffi::c_char) -> Option<u64> {
+    let payload = sys::igGetDragDropPayload() as *mut sys::ImGuiPayload;
+    if payload.is_null() || !sys::ImGuiPayload_IsDataType(payload, name) {
+        return None;
+    }
+    let token = typed_payload_data::<OwnedPayloadToken<P>>(&*payload)?.token;
+    let stored = OWNED_PAYLOADS.with(|payloads| {
+        payloads
+            .borrow()
+            .get(&any::TypeId::of::<P>())
+            .is_some_and(|stored| stored.token == token)
+    });
+    stored.then_some(token)
+}
+
 /// Indicates that an incorrect payload type was received. It is opaque,
 /// but you can view useful information with Debug formatting when
 /// `debug_assertions` are enabled.
@@ -611,3 +979,192 @@ impl std::fmt::Display for PayloadIsWrongType {
 }
 
 impl std::error::Error for PayloadIsWrongType {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Context, MouseButton};
+
+    /// Drags from the "source" button onto the "target" button, calling `f` every frame.
+    fn drag_between_buttons(ctx: &mut Context, mut f: impl FnMut(&Ui, &str)) {
+        let mut centers = [[0.0; 2]; 2];
+        for frame in 0..8 {
+            let io = ctx.io_mut();
+            match frame {
+                2 => io.add_mouse_pos_event(centers[0]),
+                3 => io.add_mouse_button_event(MouseButton::Left, true),
+                4 => io.add_mouse_pos_event([centers[0][0], centers[0][1] + 10.0]),
+                5 => io.add_mouse_pos_event(centers[1]),
+                6 => io.add_mouse_button_event(MouseButton::Left, false),
+                _ => {}
+            }
+            let ui = ctx.new_frame();
+            ui.window("Drag")
+                .position([0.0, 0.0], Condition::Always)
+                .size([200.0, 200.0], Condition::Always)
+                .build(|| {
+                    for (i, name) in ["source", "target"].into_iter().enumerate() {
+                        ui.button(name);
+                        let ([min_x, min_y], [max_x, max_y]) =
+                            (ui.item_rect_min(), ui.item_rect_max());
+                        centers[i] = [(min_x + max_x) / 2.0, (min_y + max_y) / 2.0];
+                        f(ui, name);
+                    }
+                });
+            ctx.render();
+        }
+    }
+
+    #[test]
+    fn test_owned_payload() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut made = 0;
+        let mut previews = 0;
+        let mut delivered = Vec::new();
+        drag_between_buttons(&mut ctx, |ui, name| match name {
+            "source" => {
+                let source = ui.drag_drop_source_config("PATHS");
+                if let Some(_tooltip) = source.begin_payload_with(|| {
+                    made += 1;
+                    vec![String::from("a.png"), String::from("b.png")]
+                }) {
+                    ui.text("dragging");
+                }
+            }
+            _ => {
+                if let Some(target) = ui.drag_drop_target() {
+                    let flags = DragDropFlags::ACCEPT_BEFORE_DELIVERY;
+                    assert!(target
+                        .accept_payload::<u32, _>("PATHS", flags)
+                        .unwrap()
+                        .is_err());
+                    let payload = target
+                        .accept_payload_owned::<Vec<String>, _>("PATHS", flags)
+                        .unwrap()
+                        .unwrap();
+                    if payload.delivery {
+                        delivered.extend(payload.data.unwrap());
+                    } else {
+                        assert!(payload.data.is_none());
+                        previews += 1;
+                    }
+                }
+            }
+        });
+        assert_eq!(made, 1);
+        assert!(previews > 0);
+        assert_eq!(delivered, ["a.png", "b.png"]);
+        assert!(OWNED_PAYLOADS.with(|payloads| payloads.borrow().is_empty()));
+    }
+
+    #[test]
+    fn test_drag_drop_payload_info() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut dragging = 0;
+        let mut delivered = None;
+        drag_between_buttons(&mut ctx, |ui, name| match name {
+            "source" => {
+                ui.drag_drop_source_config("INDEX").begin_payload(7u32);
+            }
+            _ => {
+                if let Some(payload) = ui.drag_drop_payload() {
+                    assert!(payload.is_data_type("INDEX"));
+                    assert!(!payload.is_data_type("IND"));
+                    assert!(!payload.is_data_type("INDEXES"));
+                    assert!(payload.holds::<u32>());
+                    assert!(!payload.holds::<i32>());
+                    assert!(!payload.holds_owned::<u32>());
+                    assert_ne!(payload.source_id(), Id::default());
+                    dragging += 1;
+                }
+                if let Some(target) = ui.drag_drop_target() {
+                    if let Some(Ok(payload)) =
+                        target.accept_payload::<u32, _>("INDEX", DragDropFlags::empty())
+                    {
+                        delivered = Some(payload.data);
+                    }
+                }
+            }
+        });
+        assert!(dragging > 0);
+        assert_eq!(delivered, Some(7));
+        assert!(ctx.new_frame().drag_drop_payload().is_none());
+        ctx.render();
+    }
+
+    #[cfg(feature = "docking")]
+    #[test]
+    fn test_drag_drop_target_rect() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut delivered = None;
+        drag_between_buttons(&mut ctx, |ui, name| match name {
+            "source" => {
+                ui.drag_drop_source_config("INDEX").begin_payload(3u32);
+            }
+            _ => {
+                // A region covering the target button, which itself is no target
+                let ([min_x, min_y], max) = (ui.item_rect_min(), ui.item_rect_max());
+                let id = ui.new_id_str("region");
+                if let Some(target) = ui.drag_drop_target_rect([min_x - 4.0, min_y - 4.0], max, id)
+                {
+                    if let Some(Ok(payload)) =
+                        target.accept_payload::<u32, _>("INDEX", DragDropFlags::empty())
+                    {
+                        delivered = Some(payload.data);
+                    }
+                }
+            }
+        });
+        assert_eq!(delivered, Some(3));
+    }
+
+    #[test]
+    fn test_external_drag() {
+        use std::path::PathBuf;
+
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let files = vec![PathBuf::from("/tmp/a.txt")];
+        let mut target_center = [0.0; 2];
+        let mut previews = 0;
+        let mut delivered = Vec::new();
+        for frame in 0..8 {
+            match frame {
+                // The drag enters the window and moves over the target
+                2 => ctx.io_mut().add_mouse_pos_event([150.0, 150.0]),
+                3 => ctx.io_mut().add_mouse_pos_event(target_center),
+                _ => {}
+            }
+            let ui = ctx.new_frame();
+            // Dropped on frame 6
+            if (2..6).contains(&frame) {
+                ui.begin_external_drag("FILES", || files.clone());
+            }
+            ui.window("Drop")
+                .position([0.0, 0.0], Condition::Always)
+                .size([200.0, 200.0], Condition::Always)
+                .build(|| {
+                    ui.button("target");
+                    let ([min_x, min_y], [max_x, max_y]) = (ui.item_rect_min(), ui.item_rect_max());
+                    target_center = [(min_x + max_x) / 2.0, (min_y + max_y) / 2.0];
+                    if let Some(target) = ui.drag_drop_target() {
+                        let payload = target
+                            .accept_payload_owned::<Vec<PathBuf>, _>(
+                                "FILES",
+                                DragDropFlags::ACCEPT_BEFORE_DELIVERY,
+                            )
+                            .unwrap()
+                            .unwrap();
+                        if payload.delivery {
+                            assert_eq!(frame, 6);
+                            delivered.extend(payload.data.unwrap());
+                        } else {
+                            previews += 1;
+                        }
+                    }
+                });
+            ctx.render();
+        }
+        assert!(previews > 0);
+        assert_eq!(delivered, files);
+    }
+}
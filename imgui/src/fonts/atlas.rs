@@ -1,6 +1,11 @@
 use bitflags::bitflags;
+use std::error::Error;
 use std::f32;
+use std::fmt;
+use std::fs;
+use std::io;
 use std::os::raw::{c_int, c_uchar, c_void};
+use std::path::PathBuf;
 use std::ptr;
 use std::rc::Rc;
 use std::slice;
@@ -75,34 +80,106 @@ pub struct FontAtlas {
 
 unsafe impl RawCast<sys::ImFontAtlas> for FontAtlas {}
 
+/// Rust-owned data referenced by the font configs of an atlas, stored in its `UserData`.
+#[derive(Default)]
+struct AtlasOwnedData {
+    glyph_ranges: Vec<Rc<[u32]>>,
+    /// Set by [`FontAtlas::set_rasterizer_density`] for the font sources added later
+    rasterizer_density: Option<f32>,
+}
+
 impl FontAtlas {
+    /// Adds a font built from one or more sources.
+    ///
+    /// Glyphs from all sources after the first are merged into the font built from the first.
+    /// If the first source has [`FontConfig::merge_mode`] set, it is merged too, into the font
+    /// that was added last, whose id is then returned. This is how an icon font is merged into
+    /// an existing font, e.g. the default one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `font_sources` is empty, if the first source merges but the atlas contains no
+    /// font yet, or if a [`FontSource::TtfFile`] cannot be read. Use
+    /// [`try_add_font`](Self::try_add_font) to handle missing font files.
     #[doc(alias = "AddFontDefault", alias = "AddFont")]
     pub fn add_font(&mut self, font_sources: &[FontSource<'_>]) -> FontId {
-        let (head, tail) = font_sources.split_first().unwrap();
-        let font_id = self.add_font_internal(head, false);
-        for font in tail {
-            self.add_font_internal(font, true);
+        self.try_add_font(font_sources)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Adds a font like [`add_font`](Self::add_font), but returns an error instead of panicking
+    /// if a [`FontSource::TtfFile`] cannot be read.
+    ///
+    /// All files are read before anything is added, so the atlas is unchanged on error.
+    #[doc(alias = "AddFontFromFileTTF")]
+    pub fn try_add_font(
+        &mut self,
+        font_sources: &[FontSource<'_>],
+    ) -> Result<FontId, FontFileError> {
+        let file_data = font_sources
+            .iter()
+            .map(|font_source| match font_source {
+                FontSource::TtfFile { path, .. } => match fs::read(path) {
+                    Ok(data) => Ok(Some(data)),
+                    Err(error) => Err(FontFileError {
+                        path: path.clone(),
+                        error,
+                    }),
+                },
+                _ => Ok(None),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut sources = font_sources.iter().zip(&file_data);
+        let (head, head_file_data) = sources.next().unwrap();
+        let font_id = self.add_font_internal(head, head_file_data.as_deref(), false);
+        for (font, file_data) in sources {
+            self.add_font_internal(font, file_data.as_deref(), true);
         }
-        font_id
+        Ok(font_id)
     }
-    fn add_font_internal(&mut self, font_source: &FontSource<'_>, merge_mode: bool) -> FontId {
+    fn add_font_internal(
+        &mut self,
+        font_source: &FontSource<'_>,
+        file_data: Option<&[u8]>,
+        merge_mode: bool,
+    ) -> FontId {
         let mut raw_config = sys_font_config_default();
-        raw_config.MergeMode = merge_mode;
-        let raw_font = match font_source {
-            FontSource::DefaultFontData { config } => unsafe {
-                if let Some(config) = config {
-                    config.apply_to_raw_config(&mut raw_config, self.raw_mut());
-                }
-                sys::ImFontAtlas_AddFontDefault(self.raw_mut(), &raw_config)
-            },
+        let (data, size_pixels, config) = match font_source {
+            FontSource::DefaultFontData { config } => (None, None, config),
             FontSource::TtfData {
                 data,
                 size_pixels,
                 config,
-            } => {
-                if let Some(config) = config {
-                    unsafe {
-                        config.apply_to_raw_config(&mut raw_config, self.raw_mut());
+            } => (Some(*data), Some(*size_pixels), config),
+            FontSource::TtfOwned {
+                data,
+                size_pixels,
+                config,
+            } => (Some(&data[..]), Some(*size_pixels), config),
+            FontSource::TtfFile {
+                size_pixels,
+                config,
+                ..
+            } => (file_data, Some(*size_pixels), config),
+        };
+        raw_config.MergeMode = merge_mode || config.as_ref().is_some_and(|c| c.merge_mode);
+        assert!(
+            !raw_config.MergeMode || self.fonts.len() > 0,
+            "Cannot merge into a font when the atlas contains no fonts"
+        );
+        if let Some(config) = config {
+            config.apply_to_raw_config(&mut raw_config, self);
+        }
+        if let Some(density) = self.owned_data().and_then(|owned| owned.rasterizer_density) {
+            raw_config.RasterizerDensity = density;
+        }
+        let raw_font = match (data, size_pixels) {
+            (Some(data), Some(size_pixels)) => {
+                if let FontSource::TtfFile { path, .. } = font_source {
+                    if config.as_ref().is_none_or(|c| c.name.is_none()) {
+                        // Named like Dear ImGui names fonts loaded from files
+                        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                        let name = format!("{}, {:.0}px", file_name, size_pixels);
+                        set_raw_config_name(&mut raw_config, &name);
                     }
                 }
                 // We can't guarantee `data` is alive when the font atlas is built, so
@@ -116,9 +193,10 @@ impl FontAtlas {
                 raw_config.FontData = data_copy.as_mut_ptr() as *mut c_void;
                 raw_config.FontDataSize = data_copy.len() as i32;
                 raw_config.FontDataOwnedByAtlas = true;
-                raw_config.SizePixels = *size_pixels;
+                raw_config.SizePixels = size_pixels;
                 unsafe { sys::ImFontAtlas_AddFont(self.raw_mut(), &raw_config) }
             }
+            _ => unsafe { sys::ImFontAtlas_AddFontDefault(self.raw_mut(), &raw_config) },
         };
         FontId(raw_font as *const _)
     }
@@ -220,6 +298,7 @@ impl FontAtlas {
         unsafe {
             sys::ImFontAtlas_Clear(self.raw_mut());
         }
+        self.release_glyph_ranges();
     }
     /// Clears output font data (glyph storage, UV coordinates)
     #[doc(alias = "ClearFonts")]
@@ -243,6 +322,288 @@ impl FontAtlas {
         unsafe {
             sys::ImFontAtlas_ClearInputData(self.raw_mut());
         }
+        self.release_glyph_ranges();
+    }
+    fn owned_data(&self) -> Option<&AtlasOwnedData> {
+        unsafe { (self.user_data as *const AtlasOwnedData).as_ref() }
+    }
+    fn owned_data_mut(&mut self) -> &mut AtlasOwnedData {
+        if self.user_data.is_null() {
+            self.user_data = Box::into_raw(Box::<AtlasOwnedData>::default()) as *mut c_void;
+        }
+        unsafe { &mut *(self.user_data as *mut AtlasOwnedData) }
+    }
+    /// Keeps owned glyph ranges alive for as long as the font configs may point to them.
+    fn retain_glyph_ranges(&mut self, ranges: &Rc<[u32]>) {
+        let owned = self.owned_data_mut();
+        if !owned.glyph_ranges.iter().any(|r| Rc::ptr_eq(r, ranges)) {
+            owned.glyph_ranges.push(ranges.clone());
+        }
+    }
+    /// Frees the glyph ranges kept alive by [`retain_glyph_ranges`](Self::retain_glyph_ranges),
+    /// once the font configs pointing to them are cleared.
+    fn release_glyph_ranges(&mut self) {
+        if !self.user_data.is_null() {
+            self.owned_data_mut().glyph_ranges.clear();
+        }
+    }
+    /// Frees all Rust-owned data of the atlas.
+    ///
+    /// Must be called before the atlas is destroyed.
+    pub(crate) unsafe fn release_owned_data(atlas: *mut sys::ImFontAtlas) {
+        let user_data = (*atlas).UserData;
+        if !user_data.is_null() {
+            drop(Box::from_raw(user_data as *mut AtlasOwnedData));
+            (*atlas).UserData = ptr::null_mut();
+        }
+    }
+    /// Returns `true` while the atlas is in use by a frame, between `new_frame` and `render`.
+    pub(crate) fn is_locked(&self) -> bool {
+        self.locked
+    }
+    /// Sets the rasterizer density of every font source, both those added so far and those
+    /// added later, replacing [`FontConfig::rasterizer_density`].
+    ///
+    /// Fonts keep their size, but glyphs are rasterized with `density` times as many pixels so
+    /// they stay sharp when scaled up by the same factor. The texture data is cleared and gets
+    /// rebuilt by the next [`build_rgba32_texture`](Self::build_rgba32_texture) or
+    /// [`build_alpha8_texture`](Self::build_alpha8_texture) call, after which the renderer has to
+    /// upload it again.
+    ///
+    /// Sources removed with [`clear_input_data`](Self::clear_input_data) can't be rebuilt, so
+    /// this has no effect on them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `density` is not positive, or if the atlas is in use by a frame.
+    #[doc(alias = "RasterizerDensity")]
+    pub fn set_rasterizer_density(&mut self, density: f32) {
+        assert!(density > 0.0, "rasterizer density must be positive");
+        assert!(
+            !self.locked,
+            "cannot change the rasterizer density while the font atlas is in use by a frame"
+        );
+        self.owned_data_mut().rasterizer_density = Some(density);
+        if self.config_data.Size == 0 {
+            return;
+        }
+        let configs = unsafe {
+            slice::from_raw_parts_mut(self.config_data.Data, self.config_data.Size as usize)
+        };
+        for config in configs {
+            config.RasterizerDensity = density;
+        }
+        self.clear_tex_data();
+    }
+
+    /// Reserves a rectangle of the given size in the atlas texture, e.g. for an icon.
+    ///
+    /// The rectangle is packed when the atlas is built. After that, its position and UVs can be
+    /// queried with [`custom_rect`](Self::custom_rect), and its pixels written with
+    /// [`custom_rect_rgba32_mut`](Self::custom_rect_rgba32_mut) or
+    /// [`custom_rect_alpha8_mut`](Self::custom_rect_alpha8_mut) before the texture is uploaded.
+    ///
+    /// Ids are invalidated by [`clear`](Self::clear) and
+    /// [`clear_input_data`](Self::clear_input_data).
+    #[doc(alias = "AddCustomRectRegular")]
+    pub fn add_custom_rect_regular(&mut self, width: u16, height: u16) -> CustomRectId {
+        let index = unsafe {
+            sys::ImFontAtlas_AddCustomRectRegular(self.raw_mut(), width.into(), height.into())
+        };
+        CustomRectId(index)
+    }
+
+    /// Reserves a rectangle in the atlas texture and maps it to `codepoint` in `font`.
+    ///
+    /// Once the atlas is built and the rectangle's pixels are written, the glyph renders like
+    /// any other character, e.g. when mapped to a private use codepoint like `'\u{E000}'`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the font atlas does not contain the given font.
+    #[doc(alias = "AddCustomRectFontGlyph")]
+    pub fn add_custom_rect_font_glyph(
+        &mut self,
+        font: FontId,
+        codepoint: char,
+        width: u16,
+        height: u16,
+        advance_x: f32,
+        offset: [f32; 2],
+    ) -> CustomRectId {
+        let font = self
+            .get_font(font)
+            .expect("Font atlas did not contain the given font") as *const Font
+            as *mut sys::ImFont;
+        let index = unsafe {
+            sys::ImFontAtlas_AddCustomRectFontGlyph(
+                self.raw_mut(),
+                font,
+                codepoint as sys::ImWchar,
+                width.into(),
+                height.into(),
+                advance_x,
+                offset.into(),
+            )
+        };
+        CustomRectId(index)
+    }
+
+    /// Returns where a custom rectangle was packed in the texture.
+    ///
+    /// Returns `None` if the id is invalid or the atlas has not been built since the rectangle
+    /// was added.
+    #[doc(alias = "GetCustomRectByIndex", alias = "CalcCustomRectUV")]
+    pub fn custom_rect(&self, id: CustomRectId) -> Option<CustomRect> {
+        let raw = self.raw_custom_rect(id)?;
+        if !self.text_ready || raw.X == 0xFFFF {
+            return None;
+        }
+        let mut uv_min = sys::ImVec2::zero();
+        let mut uv_max = sys::ImVec2::zero();
+        unsafe {
+            sys::ImFontAtlas_CalcCustomRectUV(
+                self.raw() as *const _ as *mut _,
+                raw,
+                &mut uv_min,
+                &mut uv_max,
+            );
+        }
+        Some(CustomRect {
+            x: raw.X,
+            y: raw.Y,
+            width: raw.Width,
+            height: raw.Height,
+            uv_min: uv_min.into(),
+            uv_max: uv_max.into(),
+        })
+    }
+
+    /// Returns the pixels of a custom rectangle in the RGBA32 texture.
+    ///
+    /// Returns `None` if the rectangle is not packed or the RGBA32 texture has not been built
+    /// with [`build_rgba32_texture`](Self::build_rgba32_texture). Each pixel is `[r, g, b, a]`.
+    pub fn custom_rect_rgba32_mut(
+        &mut self,
+        id: CustomRectId,
+    ) -> Option<CustomRectPixels<'_, [u8; 4]>> {
+        let rect = self.custom_rect(id)?;
+        let pixels = self.tex_pixels_rgba32 as *mut [u8; 4];
+        unsafe { self.custom_rect_pixels(rect, pixels) }
+    }
+
+    /// Returns the pixels of a custom rectangle in the alpha8 texture.
+    ///
+    /// Returns `None` if the rectangle is not packed or the alpha8 texture has not been built
+    /// with [`build_alpha8_texture`](Self::build_alpha8_texture).
+    pub fn custom_rect_alpha8_mut(&mut self, id: CustomRectId) -> Option<CustomRectPixels<'_, u8>> {
+        let rect = self.custom_rect(id)?;
+        let pixels = self.tex_pixels_alpha8;
+        unsafe { self.custom_rect_pixels(rect, pixels) }
+    }
+
+    fn raw_custom_rect(&self, id: CustomRectId) -> Option<&sys::ImFontAtlasCustomRect> {
+        if id.0 < 0 || id.0 >= self.custom_rects.Size {
+            return None;
+        }
+        Some(unsafe { &*self.custom_rects.Data.add(id.0 as usize) })
+    }
+
+    /// Safety: `pixels` must be null or point to a texture of `tex_width * tex_height` pixels.
+    unsafe fn custom_rect_pixels<T>(
+        &mut self,
+        rect: CustomRect,
+        pixels: *mut T,
+    ) -> Option<CustomRectPixels<'_, T>> {
+        if pixels.is_null() {
+            return None;
+        }
+        let stride = self.tex_width as usize;
+        let len = stride * self.tex_height as usize;
+        Some(CustomRectPixels {
+            data: slice::from_raw_parts_mut(pixels, len),
+            stride,
+            rect,
+        })
+    }
+}
+
+/// Identifies a rectangle added with [`FontAtlas::add_custom_rect_regular`] or
+/// [`FontAtlas::add_custom_rect_font_glyph`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CustomRectId(i32);
+
+impl CustomRectId {
+    /// Returns the index of the rectangle in the atlas.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Location of a packed custom rectangle in the font atlas texture
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CustomRect {
+    /// Left edge in pixels
+    pub x: u16,
+    /// Top edge in pixels
+    pub y: u16,
+    /// Width in pixels
+    pub width: u16,
+    /// Height in pixels
+    pub height: u16,
+    /// Texture coordinates of the top-left corner
+    pub uv_min: [f32; 2],
+    /// Texture coordinates of the bottom-right corner
+    pub uv_max: [f32; 2],
+}
+
+/// Mutable access to the pixels of a custom rectangle in the font atlas texture
+#[derive(Debug)]
+pub struct CustomRectPixels<'a, T> {
+    data: &'a mut [T],
+    stride: usize,
+    rect: CustomRect,
+}
+
+impl<T: Copy> CustomRectPixels<'_, T> {
+    /// Returns the rectangle these pixels belong to.
+    pub fn rect(&self) -> CustomRect {
+        self.rect
+    }
+
+    /// Returns row `y` of the rectangle, relative to its top edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is not less than the rectangle height.
+    pub fn row_mut(&mut self, y: u16) -> &mut [T] {
+        assert!(y < self.rect.height, "row out of bounds");
+        let start = (self.rect.y + y) as usize * self.stride + self.rect.x as usize;
+        &mut self.data[start..start + self.rect.width as usize]
+    }
+
+    /// Copies tightly packed pixels, row by row, into the rectangle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` doesn't contain exactly `width * height` pixels.
+    pub fn copy_from_slice(&mut self, pixels: &[T]) {
+        let width = self.rect.width as usize;
+        assert_eq!(
+            pixels.len(),
+            width * self.rect.height as usize,
+            "pixel count must match the rectangle size"
+        );
+        for (y, src) in pixels.chunks_exact(width.max(1)).enumerate() {
+            self.row_mut(y as u16).copy_from_slice(src);
+        }
+    }
+
+    /// Sets every pixel of the rectangle to `value`.
+    pub fn fill(&mut self, value: T) {
+        for y in 0..self.rect.height {
+            self.row_mut(y).fill(value);
+        }
     }
 }
 
@@ -298,6 +659,49 @@ pub enum FontSource<'a> {
         size_pixels: f32,
         config: Option<FontConfig>,
     },
+    /// Binary TTF/OTF font data owned by the source, e.g. a font loaded at runtime
+    ///
+    /// Unlike [`TtfData`](Self::TtfData), this doesn't borrow, so lists of `FontSource<'static>`
+    /// can be stored and used to rebuild the atlas later.
+    TtfOwned {
+        data: Vec<u8>,
+        size_pixels: f32,
+        config: Option<FontConfig>,
+    },
+    /// A TTF/OTF font file, read when the font is added to the atlas
+    ///
+    /// Without a [`FontConfig::name`], the font is named after the file.
+    TtfFile {
+        path: PathBuf,
+        size_pixels: f32,
+        config: Option<FontConfig>,
+    },
+}
+
+/// An error reading a [`FontSource::TtfFile`].
+#[derive(Debug)]
+pub struct FontFileError {
+    /// The font file that could not be read
+    pub path: PathBuf,
+    /// The underlying error
+    pub error: io::Error,
+}
+
+impl fmt::Display for FontFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to read font file {}: {}",
+            self.path.display(),
+            self.error
+        )
+    }
+}
+
+impl Error for FontFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
 }
 
 /// Configuration settings for a font
@@ -329,11 +733,21 @@ pub struct FontConfig {
     /// make it easy to swap between e.g. a 100% and a 400% fonts for a zooming display.
     /// IMPORTANT: If you increase this it is expected that you increase font scale
     /// accordingly, otherwise quality may look lowered.
+    ///
+    /// Replaced by the density given to [`FontAtlas::set_rasterizer_density`] once that has
+    /// been called.
     pub rasterizer_density: f32,
     /// Explicitly specify the ellipsis character.
     ///
     /// With multiple font sources the first specified ellipsis is used.
     pub ellipsis_char: Option<char>,
+    /// Merge the glyphs into the font added last instead of creating a new font.
+    ///
+    /// Sources after the first in [`FontAtlas::add_font`] always merge. Setting this on the first
+    /// source merges it as well, e.g. to add an icon font to a font added earlier.
+    pub merge_mode: bool,
+    /// Index of the font within a TTF/OTF collection file
+    pub font_no: u32,
     pub name: Option<String>,
 }
 
@@ -353,41 +767,51 @@ impl Default for FontConfig {
             rasterizer_multiply: 1.0,
             rasterizer_density: 1.0,
             ellipsis_char: None,
+            merge_mode: false,
+            font_no: 0,
             name: None,
         }
     }
 }
 
 impl FontConfig {
-    fn apply_to_raw_config(&self, raw: &mut sys::ImFontConfig, atlas: *mut sys::ImFontAtlas) {
+    fn apply_to_raw_config(&self, raw: &mut sys::ImFontConfig, atlas: &mut FontAtlas) {
+        raw.FontNo = self.font_no as i32;
         raw.SizePixels = self.size_pixels;
         raw.OversampleH = self.oversample_h;
         raw.OversampleV = self.oversample_v;
         raw.PixelSnapH = self.pixel_snap_h;
         raw.GlyphExtraSpacing = self.glyph_extra_spacing.into();
         raw.GlyphOffset = self.glyph_offset.into();
-        raw.GlyphRanges = unsafe { self.glyph_ranges.to_ptr(atlas) };
+        raw.GlyphRanges = unsafe { self.glyph_ranges.to_ptr(atlas.raw_mut()) };
+        if let Some(ranges) = self.glyph_ranges.owned() {
+            atlas.retain_glyph_ranges(ranges);
+        }
         raw.GlyphMinAdvanceX = self.glyph_min_advance_x;
         raw.GlyphMaxAdvanceX = self.glyph_max_advance_x;
         raw.FontBuilderFlags = self.font_builder_flags;
         raw.RasterizerMultiply = self.rasterizer_multiply;
-        raw.RasterizerMultiply = self.rasterizer_density;
+        raw.RasterizerDensity = self.rasterizer_density;
         // char is used as "unset" for EllipsisChar
         raw.EllipsisChar = self.ellipsis_char.map(|c| c as u32).unwrap_or(!0);
         if let Some(name) = self.name.as_ref() {
-            let bytes = name.as_bytes();
-            let mut len = bytes.len().min(raw.Name.len() - 1);
-            while !name.is_char_boundary(len) {
-                len -= 1;
-            }
-            unsafe {
-                bytes.as_ptr().copy_to(raw.Name.as_mut_ptr() as _, len);
-                raw.Name[len] = 0;
-            }
+            set_raw_config_name(raw, name);
         }
     }
 }
 
+fn set_raw_config_name(raw: &mut sys::ImFontConfig, name: &str) {
+    let bytes = name.as_bytes();
+    let mut len = bytes.len().min(raw.Name.len() - 1);
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+    unsafe {
+        bytes.as_ptr().copy_to(raw.Name.as_mut_ptr() as _, len);
+        raw.Name[len] = 0;
+    }
+}
+
 fn sys_font_config_default() -> sys::ImFontConfig {
     unsafe {
         let heap_allocated = sys::ImFontConfig_ImFontConfig();
@@ -397,6 +821,50 @@ fn sys_font_config_default() -> sys::ImFontConfig {
     }
 }
 
+#[test]
+fn test_font_atlas_custom_rects() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let fonts = ctx.fonts();
+    let font = fonts.add_font(&[FontSource::DefaultFontData { config: None }]);
+    let icon = fonts.add_custom_rect_regular(4, 3);
+    let glyph = fonts.add_custom_rect_font_glyph(font, '\u{E000}', 6, 7, 8.0, [0.0, 1.0]);
+    assert_eq!(fonts.custom_rect(icon), None);
+    assert_eq!(fonts.custom_rect(CustomRectId(99)), None);
+
+    let (width, height) = {
+        let texture = fonts.build_rgba32_texture();
+        (texture.width, texture.height)
+    };
+    let rect = fonts.custom_rect(icon).unwrap();
+    assert_eq!((rect.width, rect.height), (4, 3));
+    assert_eq!(rect.uv_min[0], rect.x as f32 / width as f32);
+    assert_eq!(rect.uv_max[1], (rect.y + 3) as f32 / height as f32);
+
+    let mut pixels = fonts.custom_rect_rgba32_mut(icon).unwrap();
+    let red = [[255, 0, 0, 255]; 12];
+    pixels.copy_from_slice(&red);
+    pixels.row_mut(2)[3] = [0, 0, 255, 255];
+    fonts
+        .custom_rect_rgba32_mut(glyph)
+        .unwrap()
+        .fill([0, 255, 0, 255]);
+
+    let texture = fonts.build_rgba32_texture();
+    let pixel = |x: u16, y: u16| {
+        let offset = (y as usize * texture.width as usize + x as usize) * 4;
+        &texture.data[offset..offset + 4]
+    };
+    assert_eq!(pixel(rect.x, rect.y), [255, 0, 0, 255]);
+    assert_eq!(pixel(rect.x + 3, rect.y + 2), [0, 0, 255, 255]);
+
+    let glyph_rect = fonts.custom_rect(glyph).unwrap();
+    let glyph_info = fonts.get_font(font).unwrap().get_glyph('\u{E000}');
+    assert_eq!(glyph_info.codepoint(), 0xE000);
+    assert_eq!(glyph_info.advance_x, 8.0);
+    assert_eq!([glyph_info.u0, glyph_info.v0], glyph_rect.uv_min);
+    assert_eq!([glyph_info.u1, glyph_info.v1], glyph_rect.uv_max);
+}
+
 #[test]
 fn test_font_config_default() {
     let sys_font_config = sys_font_config_default();
@@ -431,6 +899,116 @@ fn test_font_config_default() {
         font_config.rasterizer_multiply,
         sys_font_config.RasterizerMultiply
     );
+    assert_eq!(
+        font_config.rasterizer_density,
+        sys_font_config.RasterizerDensity
+    );
+    assert_eq!(font_config.merge_mode, sys_font_config.MergeMode);
+    assert_eq!(font_config.font_no as i32, sys_font_config.FontNo);
+}
+
+#[test]
+#[should_panic(expected = "in use by a frame")]
+fn test_rasterizer_density_during_frame() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.new_frame();
+    ctx.fonts().set_rasterizer_density(2.0);
+}
+
+#[test]
+fn test_font_atlas_merge_mode() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let fonts = ctx.fonts();
+    let text = fonts.add_font(&[FontSource::DefaultFontData { config: None }]);
+    let icons = fonts.add_font(&[FontSource::DefaultFontData {
+        config: Some(FontConfig {
+            merge_mode: true,
+            glyph_ranges: FontGlyphRanges::private_use_area(),
+            ..FontConfig::default()
+        }),
+    }]);
+    assert_eq!(icons, text);
+    assert_eq!(fonts.fonts(), vec![text]);
+    fonts.build_rgba32_texture();
+    assert_eq!(fonts.get_font(text).unwrap().config_data_count, 2);
+}
+
+#[test]
+fn test_font_atlas_keeps_owned_glyph_ranges() {
+    use crate::FontGlyphRangesBuilder;
+
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let fonts = ctx.fonts();
+    let glyph_ranges = FontGlyphRangesBuilder::new().add_text("abc").build();
+    let font = fonts.add_font(&[FontSource::DefaultFontData {
+        config: Some(FontConfig {
+            glyph_ranges,
+            ..FontConfig::default()
+        }),
+    }]);
+    assert!(!fonts.user_data.is_null());
+
+    // The ranges are only read when the atlas is built, long after they were dropped here
+    fonts.build_rgba32_texture();
+    fonts.set_rasterizer_density(2.0);
+    fonts.build_rgba32_texture();
+    let font = fonts.get_font(font).unwrap();
+    assert_eq!(font.get_glyph('b').codepoint(), 'b' as u32);
+    assert_ne!(font.get_glyph('z').codepoint(), 'z' as u32);
+
+    fonts.clear_input_data();
+    assert!(fonts.owned_data().unwrap().glyph_ranges.is_empty());
+}
+
+#[test]
+fn test_font_atlas_owned_and_file_sources() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let fonts = ctx.fonts();
+    let config_data = |fonts: &FontAtlas| unsafe {
+        let config = &*fonts
+            .config_data
+            .Data
+            .add(fonts.config_data.Size as usize - 1);
+        let data =
+            slice::from_raw_parts(config.FontData as *const u8, config.FontDataSize as usize);
+        let name = std::ffi::CStr::from_ptr(config.Name.as_ptr());
+        (
+            data.to_vec(),
+            config.FontNo,
+            name.to_str().unwrap().to_owned(),
+        )
+    };
+
+    // Font data is only parsed when the atlas is built, which these tests don't do
+    fonts.add_font(&[FontSource::TtfOwned {
+        data: b"owned".to_vec(),
+        size_pixels: 13.0,
+        config: Some(FontConfig {
+            font_no: 2,
+            ..FontConfig::default()
+        }),
+    }]);
+    assert_eq!(config_data(fonts), (b"owned".to_vec(), 2, String::new()));
+
+    let path = std::env::temp_dir().join(format!("imgui-rs-font-{}.ttf", std::process::id()));
+    std::fs::write(&path, b"from file").unwrap();
+    let source = FontSource::TtfFile {
+        path: path.clone(),
+        size_pixels: 15.0,
+        config: None,
+    };
+    fonts.add_font(std::slice::from_ref(&source));
+    std::fs::remove_file(&path).unwrap();
+    let name = format!("{}, 15px", path.file_name().unwrap().to_str().unwrap());
+    assert_eq!(config_data(fonts), (b"from file".to_vec(), 0, name));
+
+    let error = fonts
+        .try_add_font(&[FontSource::DefaultFontData { config: None }, source])
+        .unwrap_err();
+    assert_eq!(error.path, path);
+    assert_eq!(error.error.kind(), io::ErrorKind::NotFound);
+    assert_eq!(fonts.config_data.Size, 2);
+    fonts.clear();
 }
 
 /// Handle to a font atlas texture
@@ -486,7 +1064,10 @@ impl Drop for SharedFontAtlas {
     fn drop(&mut self) {
         // if we're about to drop the last one...
         if Rc::strong_count(&self.0) == 1 {
-            unsafe { sys::ImFontAtlas_destroy(*self.0) };
+            unsafe {
+                FontAtlas::release_owned_data(*self.0);
+                sys::ImFontAtlas_destroy(*self.0);
+            }
         }
     }
 }
@@ -1,3 +1,7 @@
+use std::os::raw::c_void;
+use std::rc::Rc;
+use std::slice;
+
 use crate::sys;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -11,6 +15,7 @@ enum FontGlyphRangeData {
     Thai,
     Vietnamese,
     Custom(*const sys::ImWchar),
+    Owned(Rc<[u32]>),
 }
 
 /// A set of Unicode codepoints
@@ -45,6 +50,23 @@ impl FontGlyphRanges {
     pub fn vietnamese() -> FontGlyphRanges {
         FontGlyphRanges(FontGlyphRangeData::Vietnamese)
     }
+    /// The Basic Multilingual Plane private use area, U+E000 to U+F8FF.
+    ///
+    /// Icon fonts such as Font Awesome, Material Icons and Material Symbols place their glyphs
+    /// here. Use it with [`FontConfig::merge_mode`](crate::FontConfig::merge_mode) to merge only
+    /// the icons into a text font.
+    pub fn private_use_area() -> FontGlyphRanges {
+        static RANGES: [u32; 3] = [0xE000, 0xF8FF, 0];
+        unsafe { FontGlyphRanges::from_slice_unchecked(&RANGES) }
+    }
+    /// The supplementary private use areas, U+F0000 to U+10FFFD.
+    ///
+    /// Used by icon fonts with more glyphs than fit into
+    /// [`private_use_area`](Self::private_use_area), such as Material Design Icons.
+    pub fn supplementary_private_use_area() -> FontGlyphRanges {
+        static RANGES: [u32; 5] = [0xF0000, 0xFFFFD, 0x100000, 0x10FFFD, 0];
+        unsafe { FontGlyphRanges::from_slice_unchecked(&RANGES) }
+    }
 
     /// Creates a glyph range from a static slice. The expected format is a series of pairs of
     /// non-zero codepoints, each representing an inclusive range, followed by a single
@@ -63,62 +85,25 @@ impl FontGlyphRanges {
     // should accept `char` (we'd still have to check that the range doesn't
     // fully contain the surrogate range though)
     pub fn from_slice(slice: &'static [u32]) -> FontGlyphRanges {
-        assert_eq!(
-            slice.len() % 2,
-            1,
-            "The length of a glyph range must be odd."
-        );
-        assert_eq!(
-            slice.last(),
-            Some(&0),
-            "A glyph range must be zero-terminated."
-        );
-
-        for (i, &glyph) in slice.iter().enumerate().take(slice.len() - 1) {
-            assert_ne!(
-                glyph, 0,
-                "A glyph in a range cannot be zero. \
-                 (Glyph is zero at index {})",
-                i
-            );
-            assert!(
-                glyph <= core::char::MAX as u32,
-                "A glyph in a range cannot exceed the maximum codepoint. \
-                 (Glyph is {:#x} at index {})",
-                glyph,
-                i,
-            );
-        }
-
-        let mut ranges = Vec::new();
-        for i in 0..slice.len() / 2 {
-            let (start, end) = (slice[i * 2], slice[i * 2 + 1]);
-            assert!(
-                start <= end,
-                "The start of a range cannot be larger than its end. \
-                 (At index {}, {} > {})",
-                i * 2,
-                start,
-                end
-            );
-            ranges.push((start, end));
-        }
-        ranges.sort_unstable_by_key(|x| x.0);
-        for i in 0..ranges.len() - 1 {
-            let (range_a, range_b) = (ranges[i], ranges[i + 1]);
-            if range_a.1 >= range_b.0 {
-                panic!(
-                    "The glyph ranges {:?} and {:?} overlap between {:?}.",
-                    range_a,
-                    range_b,
-                    (range_a.1, range_b.0)
-                );
-            }
-        }
-
+        validate_ranges(slice);
         unsafe { FontGlyphRanges::from_slice_unchecked(slice) }
     }
 
+    /// Creates a glyph range that owns its codepoints, in the same format as
+    /// [`FontGlyphRanges::from_slice`].
+    ///
+    /// Unlike a slice, the ranges don't need to be `'static`: every font atlas they are added to
+    /// keeps them alive until it is cleared or destroyed.
+    ///
+    /// Panics
+    /// ======
+    ///
+    /// This function will panic if the given ranges are not a valid font range.
+    pub fn from_vec(ranges: Vec<u32>) -> FontGlyphRanges {
+        validate_ranges(&ranges);
+        FontGlyphRanges(FontGlyphRangeData::Owned(ranges.into()))
+    }
+
     /// Creates a glyph range from a static slice without checking its validity.
     ///
     /// See [`FontGlyphRanges::from_slice`] for more information.
@@ -155,6 +140,15 @@ impl FontGlyphRanges {
             FontGlyphRangeData::Thai => sys::ImFontAtlas_GetGlyphRangesThai(atlas),
             FontGlyphRangeData::Vietnamese => sys::ImFontAtlas_GetGlyphRangesVietnamese(atlas),
             FontGlyphRangeData::Custom(ptr) => ptr,
+            FontGlyphRangeData::Owned(ref ranges) => ranges.as_ptr(),
+        }
+    }
+
+    /// Returns the ranges if they are owned, and therefore have to be kept alive by the atlas.
+    pub(crate) fn owned(&self) -> Option<&Rc<[u32]>> {
+        match self.0 {
+            FontGlyphRangeData::Owned(ref ranges) => Some(ranges),
+            _ => None,
         }
     }
 }
@@ -165,3 +159,191 @@ impl Default for FontGlyphRanges {
         FontGlyphRanges(FontGlyphRangeData::Default)
     }
 }
+
+fn validate_ranges(slice: &[u32]) {
+    assert_eq!(
+        slice.len() % 2,
+        1,
+        "The length of a glyph range must be odd."
+    );
+    assert_eq!(
+        slice.last(),
+        Some(&0),
+        "A glyph range must be zero-terminated."
+    );
+
+    for (i, &glyph) in slice.iter().enumerate().take(slice.len() - 1) {
+        assert_ne!(
+            glyph, 0,
+            "A glyph in a range cannot be zero. \
+                 (Glyph is zero at index {})",
+            i
+        );
+        assert!(
+            glyph <= core::char::MAX as u32,
+            "A glyph in a range cannot exceed the maximum codepoint. \
+                 (Glyph is {:#x} at index {})",
+            glyph,
+            i,
+        );
+    }
+
+    let mut ranges = Vec::new();
+    for i in 0..slice.len() / 2 {
+        let (start, end) = (slice[i * 2], slice[i * 2 + 1]);
+        assert!(
+            start <= end,
+            "The start of a range cannot be larger than its end. \
+                 (At index {}, {} > {})",
+            i * 2,
+            start,
+            end
+        );
+        ranges.push((start, end));
+    }
+    ranges.sort_unstable_by_key(|x| x.0);
+    for i in 0..ranges.len() - 1 {
+        let (range_a, range_b) = (ranges[i], ranges[i + 1]);
+        if range_a.1 >= range_b.0 {
+            panic!(
+                "The glyph ranges {:?} and {:?} overlap between {:?}.",
+                range_a,
+                range_b,
+                (range_a.1, range_b.0)
+            );
+        }
+    }
+}
+
+/// Builds glyph ranges from the text and characters an application actually displays.
+///
+/// Loading a full CJK range rasterizes tens of thousands of glyphs. Collecting the characters
+/// in use, e.g. from translation files, keeps the font texture small:
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let font_data: &[u8] = &[];
+/// # let translations = ["日本語", "设置"];
+/// let mut builder = FontGlyphRangesBuilder::new();
+/// builder.add_ranges(&FontGlyphRanges::default());
+/// for text in translations {
+///     builder.add_text(text);
+/// }
+/// let glyph_ranges = builder.build();
+///
+/// ctx.fonts().add_font(&[FontSource::TtfData {
+///     data: font_data,
+///     size_pixels: 16.0,
+///     config: Some(FontConfig {
+///         glyph_ranges,
+///         ..FontConfig::default()
+///     }),
+/// }]);
+/// ```
+#[doc(alias = "ImFontGlyphRangesBuilder")]
+#[derive(Debug)]
+pub struct FontGlyphRangesBuilder {
+    raw: *mut sys::ImFontGlyphRangesBuilder,
+}
+
+impl FontGlyphRangesBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> FontGlyphRangesBuilder {
+        FontGlyphRangesBuilder {
+            raw: unsafe { sys::ImFontGlyphRangesBuilder_ImFontGlyphRangesBuilder() },
+        }
+    }
+    /// Adds every character of `text`.
+    #[doc(alias = "AddText")]
+    pub fn add_text(&mut self, text: &str) -> &mut Self {
+        let range = text.as_bytes().as_ptr_range();
+        unsafe {
+            sys::ImFontGlyphRangesBuilder_AddText(self.raw, range.start as _, range.end as _);
+        }
+        self
+    }
+    /// Adds a single character.
+    #[doc(alias = "AddChar")]
+    pub fn add_char(&mut self, c: char) -> &mut Self {
+        unsafe { sys::ImFontGlyphRangesBuilder_AddChar(self.raw, c as sys::ImWchar) };
+        self
+    }
+    /// Adds every character in `ranges`, e.g. one of the presets.
+    #[doc(alias = "AddRanges")]
+    pub fn add_ranges(&mut self, ranges: &FontGlyphRanges) -> &mut Self {
+        // The presets are returned by `ImFontAtlas` methods, but are static data that doesn't
+        // depend on the atlas, so an empty one that never allocates is enough to look them up.
+        let mut atlas = sys::ImFontAtlas::default();
+        unsafe { sys::ImFontGlyphRangesBuilder_AddRanges(self.raw, ranges.to_ptr(&mut atlas)) };
+        self
+    }
+    /// Returns `true` if `c` has been added.
+    #[doc(alias = "GetBit")]
+    pub fn contains(&self, c: char) -> bool {
+        unsafe { sys::ImFontGlyphRangesBuilder_GetBit(self.raw, c as usize) }
+    }
+    /// Removes every character added so far.
+    #[doc(alias = "Clear")]
+    pub fn clear(&mut self) {
+        unsafe { sys::ImFontGlyphRangesBuilder_Clear(self.raw) };
+    }
+    /// Returns the smallest set of ranges covering every character added so far.
+    ///
+    /// The ranges are owned, see [`FontGlyphRanges::from_vec`].
+    #[doc(alias = "BuildRanges")]
+    pub fn build(&self) -> FontGlyphRanges {
+        unsafe {
+            let mut out = sys::ImVector_ImWchar {
+                Size: 0,
+                Capacity: 0,
+                Data: std::ptr::null_mut(),
+            };
+            sys::ImFontGlyphRangesBuilder_BuildRanges(self.raw, &mut out);
+            let ranges: Rc<[u32]> = slice::from_raw_parts(out.Data, out.Size as usize).into();
+            sys::igMemFree(out.Data as *mut c_void);
+            FontGlyphRanges(FontGlyphRangeData::Owned(ranges))
+        }
+    }
+}
+
+impl Default for FontGlyphRangesBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for FontGlyphRangesBuilder {
+    fn drop(&mut self) {
+        unsafe { sys::ImFontGlyphRangesBuilder_destroy(self.raw) };
+    }
+}
+
+#[test]
+fn test_font_glyph_ranges_builder() {
+    // The builder allocates through Dear ImGui, which counts allocations in the current context
+    let (_guard, _ctx) = crate::test::test_ctx();
+    let mut builder = FontGlyphRangesBuilder::new();
+    builder.add_text("cab é").add_char('\u{1F600}');
+    assert!(builder.contains('é'));
+    assert!(!builder.contains('d'));
+    let ranges = builder.build();
+    assert_eq!(
+        ranges.owned().unwrap()[..],
+        [0x20, 0x20, 0x61, 0x63, 0xE9, 0xE9, 0x1F600, 0x1F600, 0]
+    );
+
+    builder.clear();
+    builder.add_ranges(&FontGlyphRanges::from_vec(vec![0x30, 0x39, 0]));
+    builder.add_ranges(&FontGlyphRanges::default());
+    assert!(builder.contains('5'));
+    assert!(builder.contains('ÿ'));
+    assert!(!builder.contains('€'));
+    assert!(!builder.contains('\u{1F600}'));
+}
+
+#[test]
+#[should_panic(expected = "must be zero-terminated")]
+fn test_font_glyph_ranges_from_vec_invalid() {
+    FontGlyphRanges::from_vec(vec![0x20, 0x7E, 0x80]);
+}
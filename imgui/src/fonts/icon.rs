@@ -0,0 +1,160 @@
+use std::fmt;
+
+/// A codepoint in an icon font, usable directly as a label.
+///
+/// Icon fonts such as Font Awesome or Material Symbols map their glyphs to private use
+/// codepoints. Merge the icon font into a text font with [`FontConfig::merge_mode`] and
+/// [`FontGlyphRanges::private_use_area`], then name the icons you use as constants:
+///
+/// ```no_run
+/// # use imgui::*;
+/// const ICON_FLOPPY_DISK: Icon = Icon::new(0xF0C7);
+///
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// ui.button(ICON_FLOPPY_DISK);
+/// ui.button(ICON_FLOPPY_DISK.with_label("Save"));
+/// ui.text(format!("{} Saved", ICON_FLOPPY_DISK));
+/// ```
+///
+/// [`FontConfig::merge_mode`]: crate::FontConfig::merge_mode
+/// [`FontGlyphRanges::private_use_area`]: crate::FontGlyphRanges::private_use_area
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Icon {
+    ch: char,
+    utf8: [u8; 4],
+    len: u8,
+}
+
+impl Icon {
+    /// Creates an icon from its codepoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `codepoint` is not a valid Unicode scalar value. In a constant, this is a
+    /// compile error instead.
+    pub const fn new(codepoint: u32) -> Icon {
+        match char::from_u32(codepoint) {
+            Some(ch) => Icon::from_char(ch),
+            None => panic!("icon codepoint is not a valid char"),
+        }
+    }
+
+    /// Creates an icon from a `char`.
+    pub const fn from_char(ch: char) -> Icon {
+        let c = ch as u32;
+        let (utf8, len) = if c < 0x80 {
+            ([c as u8, 0, 0, 0], 1)
+        } else if c < 0x800 {
+            ([0xC0 | (c >> 6) as u8, 0x80 | (c & 0x3F) as u8, 0, 0], 2)
+        } else if c < 0x10000 {
+            (
+                [
+                    0xE0 | (c >> 12) as u8,
+                    0x80 | ((c >> 6) & 0x3F) as u8,
+                    0x80 | (c & 0x3F) as u8,
+                    0,
+                ],
+                3,
+            )
+        } else {
+            (
+                [
+                    0xF0 | (c >> 18) as u8,
+                    0x80 | ((c >> 12) & 0x3F) as u8,
+                    0x80 | ((c >> 6) & 0x3F) as u8,
+                    0x80 | (c & 0x3F) as u8,
+                ],
+                4,
+            )
+        };
+        Icon { ch, utf8, len }
+    }
+
+    /// Returns the icon as a `char`.
+    #[inline]
+    pub const fn char(self) -> char {
+        self.ch
+    }
+
+    /// Returns the codepoint of the icon.
+    #[inline]
+    pub const fn codepoint(self) -> u32 {
+        self.ch as u32
+    }
+
+    /// Returns the icon as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // Safety: `utf8` was encoded from a valid char
+        unsafe { std::str::from_utf8_unchecked(&self.utf8[..self.len as usize]) }
+    }
+
+    /// Returns `true` if the icon lies in one of the Unicode private use areas, where icon
+    /// fonts place their glyphs.
+    pub const fn is_private_use(self) -> bool {
+        matches!(self.ch, '\u{E000}'..='\u{F8FF}' | '\u{F0000}'..='\u{10FFFD}')
+    }
+
+    /// Returns the icon followed by a space and `label`, e.g. for a button.
+    pub fn with_label(self, label: &str) -> String {
+        format!("{} {}", self, label)
+    }
+}
+
+impl AsRef<str> for Icon {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<Icon> for char {
+    #[inline]
+    fn from(icon: Icon) -> char {
+        icon.ch
+    }
+}
+
+impl fmt::Display for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Icon(U+{:04X})", self.codepoint())
+    }
+}
+
+#[test]
+fn test_icon_encoding() {
+    for ch in [
+        'a',
+        'é',
+        '\u{E000}',
+        '\u{F0C7}',
+        '\u{F8FF}',
+        '\u{F0001}',
+        '\u{10FFFD}',
+    ] {
+        let icon = Icon::from_char(ch);
+        let mut buf = [0; 4];
+        assert_eq!(icon.as_str(), ch.encode_utf8(&mut buf));
+        assert_eq!(icon.char(), ch);
+        assert_eq!(Icon::new(ch as u32), icon);
+    }
+
+    const SAVE: Icon = Icon::new(0xF0C7);
+    assert!(SAVE.is_private_use());
+    assert!(!Icon::from_char('a').is_private_use());
+    assert_eq!(SAVE.with_label("Save"), "\u{F0C7} Save");
+    assert_eq!(format!("{:?}", SAVE), "Icon(U+F0C7)");
+}
+
+#[test]
+#[should_panic(expected = "not a valid char")]
+fn test_icon_invalid_codepoint() {
+    Icon::new(0xD800);
+}
@@ -6,6 +6,9 @@ pub mod atlas;
 pub mod font;
 pub mod glyph;
 pub mod glyph_ranges;
+pub mod icon;
+#[cfg(feature = "system-fonts")]
+pub mod system;
 
 /// # Fonts
 impl Ui {
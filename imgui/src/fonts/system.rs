@@ -0,0 +1,400 @@
+//! Finding installed fonts by family name, following the fontconfig configuration.
+//!
+//! Fontconfig is how Linux and the BSDs describe where fonts are installed and which
+//! families a user prefers, e.g. for `sans-serif` or `monospace`. [`SystemFonts::load`] reads
+//! that configuration and indexes the fonts in the configured directories, without linking
+//! to the fontconfig library:
+//!
+//! ```no_run
+//! # use imgui::*;
+//! # let mut ctx = Context::create();
+//! let system_fonts = SystemFonts::load().unwrap_or_default();
+//! let font = match system_fonts.find("sans-serif") {
+//!     Some(font) => ctx.fonts().add_font(&[font.source(16.0, None)]),
+//!     None => ctx.fonts().add_font(&[FontSource::DefaultFontData { config: None }]),
+//! };
+//! ```
+//!
+//! On platforms without a fontconfig configuration, [`SystemFonts::load`] returns an error.
+//!
+//! Requires the `system-fonts` feature.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::fonts::atlas::{FontConfig, FontSource};
+
+/// The main configuration file, used unless `FONTCONFIG_FILE` is set.
+const DEFAULT_CONFIG_FILE: &str = "/etc/fonts/fonts.conf";
+
+/// How deep font directories are searched for subdirectories.
+const MAX_DIR_DEPTH: u32 = 16;
+
+/// A font face installed on the system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemFont {
+    /// The family name, e.g. `DejaVu Sans`
+    pub family: String,
+    /// The font file
+    pub path: PathBuf,
+    /// Index of the face within the file, non-zero for fonts in a collection
+    pub index: u32,
+    /// The weight, from 100 (thin) to 900 (black), where 400 is regular
+    pub weight: u16,
+    /// Whether the face is italic or oblique
+    pub italic: bool,
+}
+
+impl SystemFont {
+    /// Returns a font source that loads this face from its file.
+    ///
+    /// The face index is written to [`FontConfig::font_no`].
+    pub fn source(&self, size_pixels: f32, config: Option<FontConfig>) -> FontSource<'static> {
+        let mut config = config.unwrap_or_default();
+        config.font_no = self.index;
+        FontSource::TtfFile {
+            path: self.path.clone(),
+            size_pixels,
+            config: Some(config),
+        }
+    }
+}
+
+/// The fonts installed on the system, and the family preferences of the fontconfig
+/// configuration.
+#[derive(Clone, Debug, Default)]
+pub struct SystemFonts {
+    fonts: Vec<SystemFont>,
+    aliases: Vec<fontconfig_parser::Alias>,
+}
+
+impl SystemFonts {
+    /// Reads the fontconfig configuration and indexes the fonts it lists.
+    ///
+    /// The configuration is read from the file named by the `FONTCONFIG_FILE` environment
+    /// variable, or from `/etc/fonts/fonts.conf`, including the user configuration it refers
+    /// to. Font files that cannot be read or parsed are skipped.
+    ///
+    /// Every font file is read in full to find the faces it contains, which takes a while on
+    /// systems with many fonts or large collections such as CJK fonts, so prefer loading once
+    /// at startup or on a background thread.
+    pub fn load() -> Result<SystemFonts, SystemFontsError> {
+        match std::env::var_os("FONTCONFIG_FILE") {
+            Some(path) => SystemFonts::load_config(path),
+            None => SystemFonts::load_config(DEFAULT_CONFIG_FILE),
+        }
+    }
+
+    /// Reads the given fontconfig configuration file and indexes the fonts it lists.
+    pub fn load_config(path: impl AsRef<Path>) -> Result<SystemFonts, SystemFontsError> {
+        let path = path.as_ref();
+        let mut config = fontconfig_parser::FontConfig::default();
+        config
+            .merge_config(path)
+            .map_err(|error| SystemFontsError {
+                path: path.to_owned(),
+                error,
+            })?;
+
+        let mut fonts = Vec::new();
+        let mut visited = HashSet::new();
+        for dir in &config.dirs {
+            index_dir(&dir.path, 0, &mut visited, &mut fonts);
+        }
+        // Directories may be listed more than once, e.g. through symlinks
+        fonts.sort_by(|a, b| (&a.path, a.index).cmp(&(&b.path, b.index)));
+        fonts.dedup_by(|a, b| a.path == b.path && a.index == b.index);
+        Ok(SystemFonts {
+            fonts,
+            aliases: config.aliases,
+        })
+    }
+
+    /// Returns every indexed font face.
+    pub fn fonts(&self) -> &[SystemFont] {
+        &self.fonts
+    }
+
+    /// Finds the regular face of a family.
+    ///
+    /// See [`find_style`](Self::find_style).
+    pub fn find(&self, family: &str) -> Option<&SystemFont> {
+        self.find_style(family, 400, false)
+    }
+
+    /// Finds the face of a family closest to the given weight and slant.
+    ///
+    /// Family names are matched case-insensitively. Aliases from the configuration are
+    /// followed in the order fontconfig applies them: preferred families first, then the
+    /// family itself, then acceptable and default families. This resolves generic families
+    /// like `sans-serif` and `monospace` to the fonts the user prefers.
+    pub fn find_style(&self, family: &str, weight: u16, italic: bool) -> Option<&SystemFont> {
+        let mut families = Vec::new();
+        self.expand_family(family, &mut Vec::new(), &mut families);
+        families.iter().find_map(|family| {
+            self.fonts
+                .iter()
+                .filter(|font| font.family.eq_ignore_ascii_case(family))
+                .min_by_key(|font| (font.italic != italic, font.weight.abs_diff(weight)))
+        })
+    }
+
+    /// Appends `family` and its aliases to `out`, in order of preference.
+    fn expand_family<'a>(
+        &'a self,
+        family: &'a str,
+        // Families being expanded, configurations commonly alias a family to itself
+        expanding: &mut Vec<&'a str>,
+        out: &mut Vec<&'a str>,
+    ) {
+        let contains = |families: &[&str]| families.iter().any(|f| f.eq_ignore_ascii_case(family));
+        if contains(out) || contains(expanding) {
+            return;
+        }
+        expanding.push(family);
+        let aliases = || {
+            self.aliases
+                .iter()
+                .filter(move |alias| alias.alias.eq_ignore_ascii_case(family))
+        };
+        for alias in aliases() {
+            for prefer in &alias.prefer {
+                self.expand_family(prefer, expanding, out);
+            }
+        }
+        out.push(family);
+        for alias in aliases() {
+            for accept in &alias.accept {
+                self.expand_family(accept, expanding, out);
+            }
+        }
+        for alias in aliases() {
+            for default in &alias.default {
+                self.expand_family(default, expanding, out);
+            }
+        }
+        expanding.pop();
+    }
+}
+
+fn index_dir(
+    dir: &Path,
+    depth: u32,
+    // Canonical paths, since symlinks can lead to a directory seen before or to a parent
+    visited: &mut HashSet<PathBuf>,
+    fonts: &mut Vec<SystemFont>,
+) {
+    let Ok(canonical) = fs::canonicalize(dir) else {
+        return;
+    };
+    if !visited.insert(canonical) {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if depth < MAX_DIR_DEPTH {
+                index_dir(&path, depth + 1, visited, fonts);
+            }
+            continue;
+        }
+        let is_font = path.extension().is_some_and(|ext| {
+            ["ttf", "otf", "ttc", "otc"]
+                .iter()
+                .any(|font_ext| ext.eq_ignore_ascii_case(font_ext))
+        });
+        if is_font {
+            if let Ok(data) = fs::read(&path) {
+                index_file(&path, &data, fonts);
+            }
+        }
+    }
+}
+
+fn index_file(path: &Path, data: &[u8], fonts: &mut Vec<SystemFont>) {
+    let count = ttf_parser::fonts_in_collection(data).unwrap_or(1);
+    for index in 0..count {
+        let Ok(face) = ttf_parser::Face::parse(data, index) else {
+            continue;
+        };
+        if let Some(family) = family_name(&face) {
+            fonts.push(SystemFont {
+                family,
+                path: path.to_owned(),
+                index,
+                weight: face.weight().to_number(),
+                italic: face.is_italic() || face.is_oblique(),
+            });
+        }
+    }
+}
+
+/// Returns the English family name of a face, preferring the typographic family, which
+/// groups more than the four styles the legacy family name allows.
+fn family_name(face: &ttf_parser::Face<'_>) -> Option<String> {
+    let find = |name_id| {
+        face.names()
+            .into_iter()
+            .filter(|name| name.name_id == name_id && name.is_unicode())
+            .find(|name| name.language() == ttf_parser::Language::English_UnitedStates)
+            .and_then(|name| name.to_string())
+    };
+    find(ttf_parser::name_id::TYPOGRAPHIC_FAMILY).or_else(|| find(ttf_parser::name_id::FAMILY))
+}
+
+/// An error reading the fontconfig configuration.
+#[derive(Debug)]
+pub struct SystemFontsError {
+    path: PathBuf,
+    error: fontconfig_parser::Error,
+}
+
+impl SystemFontsError {
+    /// Returns the configuration file that could not be read.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl fmt::Display for SystemFontsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to read fontconfig configuration {}: {}",
+            self.path.display(),
+            self.error
+        )
+    }
+}
+
+impl Error for SystemFontsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn font(family: &str, weight: u16, italic: bool) -> SystemFont {
+        SystemFont {
+            family: family.to_owned(),
+            path: PathBuf::from(format!("/fonts/{}-{}-{}.ttf", family, weight, italic)),
+            index: 0,
+            weight,
+            italic,
+        }
+    }
+
+    #[test]
+    fn test_system_fonts_find() {
+        let conf = r#"<?xml version="1.0"?>
+            <!DOCTYPE fontconfig SYSTEM "urn:fontconfig:fonts.dtd">
+            <fontconfig>
+                <dir>/nonexistent/imgui-rs/fonts</dir>
+                <alias>
+                    <family>sans-serif</family>
+                    <prefer><family>Missing Sans</family><family>Noto Sans</family></prefer>
+                    <default><family>DejaVu Sans</family></default>
+                </alias>
+                <alias>
+                    <family>monospace</family>
+                    <prefer><family>monospace</family></prefer>
+                    <accept><family>DejaVu Sans Mono</family></accept>
+                </alias>
+            </fontconfig>"#;
+        let path = std::env::temp_dir().join(format!("imgui-rs-fonts-{}.conf", std::process::id()));
+        fs::write(&path, conf).unwrap();
+        let mut system_fonts = SystemFonts::load_config(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(system_fonts.fonts().is_empty());
+        assert_eq!(system_fonts.aliases.len(), 2);
+
+        system_fonts.fonts = vec![
+            font("DejaVu Sans", 400, false),
+            font("DejaVu Sans Mono", 400, false),
+            font("Noto Sans", 700, false),
+            font("Noto Sans", 400, true),
+            font("Noto Sans", 400, false),
+        ];
+        assert_eq!(
+            system_fonts.find("sans-serif"),
+            Some(&font("Noto Sans", 400, false))
+        );
+        assert_eq!(
+            system_fonts.find_style("SANS-SERIF", 600, false),
+            Some(&font("Noto Sans", 700, false))
+        );
+        assert_eq!(
+            system_fonts.find_style("noto sans", 400, true),
+            Some(&font("Noto Sans", 400, true))
+        );
+        assert_eq!(
+            system_fonts.find("monospace"),
+            Some(&font("DejaVu Sans Mono", 400, false))
+        );
+        assert_eq!(system_fonts.find("Missing Serif"), None);
+
+        let mut families = Vec::new();
+        system_fonts.expand_family("sans-serif", &mut Vec::new(), &mut families);
+        assert_eq!(
+            families,
+            ["Missing Sans", "Noto Sans", "sans-serif", "DejaVu Sans"]
+        );
+
+        system_fonts.fonts.retain(|font| font.family != "Noto Sans");
+        assert_eq!(
+            system_fonts.find("sans-serif"),
+            Some(&font("DejaVu Sans", 400, false))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_system_fonts_symlink_cycle() {
+        let root = std::env::temp_dir().join(format!("imgui-rs-fonts-{}", std::process::id()));
+        let nested = root.join("a/b");
+        fs::create_dir_all(&nested).unwrap();
+        std::os::unix::fs::symlink(&root, nested.join("parent")).unwrap();
+        std::os::unix::fs::symlink(&nested, root.join("nested")).unwrap();
+        fs::write(nested.join("broken.ttf"), b"not a font").unwrap();
+
+        let mut visited = HashSet::new();
+        let mut fonts = Vec::new();
+        index_dir(&root, 0, &mut visited, &mut fonts);
+        fs::remove_dir_all(&root).unwrap();
+        assert!(fonts.is_empty());
+        assert_eq!(visited.len(), 3);
+    }
+
+    #[test]
+    fn test_system_fonts_missing_config() {
+        let error = SystemFonts::load_config("/nonexistent/imgui-rs/fonts.conf").unwrap_err();
+        assert_eq!(error.path(), Path::new("/nonexistent/imgui-rs/fonts.conf"));
+    }
+
+    #[test]
+    fn test_system_font_source() {
+        let mut font = font("Noto Sans CJK", 400, false);
+        font.index = 3;
+        match font.source(18.0, None) {
+            FontSource::TtfFile {
+                path,
+                size_pixels,
+                config,
+            } => {
+                assert_eq!(path, font.path);
+                assert_eq!(size_pixels, 18.0);
+                assert_eq!(config.unwrap().font_no, 3);
+            }
+            _ => panic!("expected a font file source"),
+        }
+    }
+}
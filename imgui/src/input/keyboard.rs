@@ -1,3 +1,7 @@
+use std::ops::BitOr;
+
+use bitflags::bitflags;
+
 use crate::sys;
 use crate::Ui;
 
@@ -350,6 +354,144 @@ impl FocusedWidget {
     }
 }
 
+bitflags! {
+    /// Modifier keys that can be combined with a [`Key`] to form a [`KeyChord`]
+    #[repr(transparent)]
+    pub struct KeyMod: u32 {
+        /// Ctrl (Cmd on macOS when `io.config_mac_os_behaviors` is set)
+        const CTRL = sys::ImGuiMod_Ctrl;
+        /// Shift
+        const SHIFT = sys::ImGuiMod_Shift;
+        /// Alt (Option on macOS)
+        const ALT = sys::ImGuiMod_Alt;
+        /// Super (Windows/Cmd key)
+        const SUPER = sys::ImGuiMod_Super;
+    }
+}
+
+/// A key optionally combined with one or more modifiers, e.g. `Key::S | KeyMod::CTRL`.
+///
+/// A chord consisting only of modifiers (e.g. `KeyChord::from(KeyMod::CTRL)`) is valid and
+/// matches when those modifiers are held.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct KeyChord {
+    key: Option<Key>,
+    mods: KeyMod,
+}
+
+impl KeyChord {
+    /// Creates a chord from a key and a set of modifiers
+    #[inline]
+    pub const fn new(key: Key, mods: KeyMod) -> Self {
+        KeyChord {
+            key: Some(key),
+            mods,
+        }
+    }
+
+    /// Returns the non-modifier key of this chord, if any
+    #[inline]
+    pub const fn key(self) -> Option<Key> {
+        self.key
+    }
+
+    /// Returns the modifiers of this chord
+    #[inline]
+    pub const fn mods(self) -> KeyMod {
+        self.mods
+    }
+
+    /// Returns the raw `ImGuiKeyChord` value
+    #[inline]
+    pub fn raw(self) -> sys::ImGuiKeyChord {
+        let key = self.key.map_or(sys::ImGuiKey_None, |key| key as u32);
+        (key | self.mods.bits()) as sys::ImGuiKeyChord
+    }
+}
+
+impl From<Key> for KeyChord {
+    #[inline]
+    fn from(key: Key) -> Self {
+        match key {
+            Key::ModCtrl => KeyMod::CTRL.into(),
+            Key::ModShift => KeyMod::SHIFT.into(),
+            Key::ModAlt => KeyMod::ALT.into(),
+            Key::ModSuper => KeyMod::SUPER.into(),
+            key => KeyChord::new(key, KeyMod::empty()),
+        }
+    }
+}
+
+impl From<KeyMod> for KeyChord {
+    #[inline]
+    fn from(mods: KeyMod) -> Self {
+        KeyChord { key: None, mods }
+    }
+}
+
+impl BitOr<KeyMod> for Key {
+    type Output = KeyChord;
+    #[inline]
+    fn bitor(self, mods: KeyMod) -> KeyChord {
+        KeyChord::from(self) | mods
+    }
+}
+
+impl BitOr<Key> for KeyMod {
+    type Output = KeyChord;
+    #[inline]
+    fn bitor(self, key: Key) -> KeyChord {
+        key | self
+    }
+}
+
+impl BitOr<KeyMod> for KeyChord {
+    type Output = KeyChord;
+    #[inline]
+    fn bitor(self, mods: KeyMod) -> KeyChord {
+        KeyChord {
+            key: self.key,
+            mods: self.mods | mods,
+        }
+    }
+}
+
+bitflags! {
+    /// Flags for [`Ui::shortcut_with_flags`], [`Ui::set_next_item_shortcut_with_flags`] and
+    /// related functions
+    #[repr(transparent)]
+    pub struct InputFlags: u32 {
+        /// Enable repeat. Return true on successive repeats.
+        const REPEAT = sys::ImGuiInputFlags_Repeat;
+        /// Route to active item only
+        const ROUTE_ACTIVE = sys::ImGuiInputFlags_RouteActive;
+        /// Route to windows in the focus stack. This is the default for shortcuts.
+        const ROUTE_FOCUSED = sys::ImGuiInputFlags_RouteFocused;
+        /// Global route (unless a focused window or active item registered the route)
+        const ROUTE_GLOBAL = sys::ImGuiInputFlags_RouteGlobal;
+        /// Do not register route, poll keys directly
+        const ROUTE_ALWAYS = sys::ImGuiInputFlags_RouteAlways;
+        /// Option: global route: higher priority than focused route
+        const ROUTE_OVER_FOCUSED = sys::ImGuiInputFlags_RouteOverFocused;
+        /// Option: global route: higher priority than active item
+        const ROUTE_OVER_ACTIVE = sys::ImGuiInputFlags_RouteOverActive;
+        /// Option: global route: will not be applied if underlying background/void is focused
+        const ROUTE_UNLESS_BG_FOCUSED = sys::ImGuiInputFlags_RouteUnlessBgFocused;
+        /// Option: route evaluated from the point of view of root window rather than current
+        /// window
+        const ROUTE_FROM_ROOT_WINDOW = sys::ImGuiInputFlags_RouteFromRootWindow;
+        /// Automatically display a tooltip when hovering item (only for
+        /// [`Ui::set_next_item_shortcut_with_flags`])
+        const TOOLTIP = sys::ImGuiInputFlags_Tooltip;
+        /// Lock the key ownership for the rest of the frame (only for [`Ui::set_key_owner`])
+        #[cfg(feature = "docking")]
+        const LOCK_THIS_FRAME = sys::ImGuiInputFlags_LockThisFrame;
+        /// Lock the key ownership until the key is released (only for [`Ui::set_key_owner`])
+        #[cfg(feature = "docking")]
+        const LOCK_UNTIL_RELEASE = sys::ImGuiInputFlags_LockUntilRelease;
+    }
+}
+
 /// # Input: Keyboard
 impl Ui {
     /// Returns true if the key is being held.
@@ -436,4 +578,134 @@ impl Ui {
             sys::igSetKeyboardFocusHere(target_widget.as_offset());
         }
     }
+
+    /// Returns true if the key chord was pressed this frame, e.g. `Key::S | KeyMod::CTRL`.
+    ///
+    /// Unlike [`shortcut`](Self::shortcut), this does not participate in input routing.
+    #[inline]
+    #[doc(alias = "IsKeyChordPressed")]
+    pub fn is_key_chord_pressed(&self, chord: impl Into<KeyChord>) -> bool {
+        let chord = chord.into().raw();
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "docking")] {
+                unsafe { sys::igIsKeyChordPressed_Nil(chord) }
+            } else {
+                unsafe { sys::igIsKeyChordPressed(chord) }
+            }
+        }
+    }
+
+    /// Returns true if the shortcut was pressed and the current window has the route for it.
+    ///
+    /// By default shortcuts are routed to the focused window, so the same chord can be bound in
+    /// several windows without conflict. Use [`shortcut_with_flags`](Self::shortcut_with_flags)
+    /// to change the routing policy or enable repeat.
+    #[inline]
+    #[doc(alias = "Shortcut")]
+    pub fn shortcut(&self, chord: impl Into<KeyChord>) -> bool {
+        self.shortcut_with_flags(chord, InputFlags::empty())
+    }
+
+    /// Returns true if the shortcut was pressed, using the given routing flags.
+    #[inline]
+    #[doc(alias = "Shortcut")]
+    pub fn shortcut_with_flags(&self, chord: impl Into<KeyChord>, flags: InputFlags) -> bool {
+        let chord = chord.into().raw();
+        let flags = flags.bits() as sys::ImGuiInputFlags;
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "docking")] {
+                unsafe { sys::igShortcut_Nil(chord, flags) }
+            } else {
+                unsafe { sys::igShortcut(chord, flags) }
+            }
+        }
+    }
+
+    /// Binds a shortcut to the next item, which is then activated (e.g. a button is pressed)
+    /// when the chord is pressed.
+    #[inline]
+    #[doc(alias = "SetNextItemShortcut")]
+    pub fn set_next_item_shortcut(&self, chord: impl Into<KeyChord>) {
+        self.set_next_item_shortcut_with_flags(chord, InputFlags::empty());
+    }
+
+    /// Binds a shortcut to the next item, using the given routing flags.
+    ///
+    /// Pass [`InputFlags::TOOLTIP`] to show the shortcut in a tooltip when hovering the item.
+    #[inline]
+    #[doc(alias = "SetNextItemShortcut")]
+    pub fn set_next_item_shortcut_with_flags(&self, chord: impl Into<KeyChord>, flags: InputFlags) {
+        unsafe {
+            sys::igSetNextItemShortcut(chord.into().raw(), flags.bits() as sys::ImGuiInputFlags)
+        }
+    }
+
+    /// Claims ownership of the key for the last submitted item, if it is hovered or active.
+    ///
+    /// Other code polling the key with an owner check will then stop seeing it.
+    #[inline]
+    #[doc(alias = "SetItemKeyOwner")]
+    pub fn set_item_key_owner(&self, key: Key) {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "docking")] {
+                unsafe { sys::igSetItemKeyOwner_Nil(key as u32) }
+            } else {
+                unsafe { sys::igSetItemKeyOwner(key as u32) }
+            }
+        }
+    }
+
+    /// Sets the owner of a key. `None` releases ownership so that nobody owns the key.
+    ///
+    /// Only [`InputFlags::LOCK_THIS_FRAME`] and [`InputFlags::LOCK_UNTIL_RELEASE`] are
+    /// supported in `flags`.
+    #[cfg(feature = "docking")]
+    #[inline]
+    #[doc(alias = "SetKeyOwner")]
+    pub fn set_key_owner(&self, key: Key, owner: Option<crate::Id>, flags: InputFlags) {
+        // ImGuiKeyOwner_NoOwner is ((ImGuiID)-1)
+        let owner = owner.map_or(u32::MAX, |id| id.0);
+        unsafe { sys::igSetKeyOwner(key as u32, owner, flags.bits() as sys::ImGuiInputFlags) }
+    }
+}
+
+#[test]
+fn test_key_chord_composition() {
+    let chord = Key::S | KeyMod::CTRL | KeyMod::SHIFT;
+    assert_eq!(chord.key(), Some(Key::S));
+    assert_eq!(chord.mods(), KeyMod::CTRL | KeyMod::SHIFT);
+    assert_eq!(
+        chord.raw(),
+        (sys::ImGuiKey_S | sys::ImGuiMod_Ctrl | sys::ImGuiMod_Shift) as sys::ImGuiKeyChord
+    );
+    assert_eq!(KeyMod::CTRL | Key::S, Key::S | KeyMod::CTRL);
+    assert_eq!(KeyChord::from(Key::ModAlt), KeyChord::from(KeyMod::ALT));
+}
+
+#[test]
+fn test_shortcut() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut pressed = Vec::new();
+    for frame in 0..3 {
+        match frame {
+            1 => {
+                ctx.io_mut().add_key_event(Key::ModCtrl, true);
+                ctx.io_mut().add_key_event(Key::S, true);
+            }
+            2 => {
+                ctx.io_mut().add_key_event(Key::S, false);
+                ctx.io_mut().add_key_event(Key::ModCtrl, false);
+            }
+            _ => {}
+        }
+        let ui = ctx.new_frame();
+        ui.window("Editor").build(|| {
+            pressed.push((
+                ui.shortcut(Key::S | KeyMod::CTRL),
+                ui.is_key_chord_pressed(Key::S | KeyMod::CTRL),
+            ));
+        });
+        ctx.render();
+    }
+    assert_eq!(pressed, vec![(false, false), (true, true), (false, false)]);
 }
@@ -94,6 +94,8 @@ pub use self::clipboard::*;
 pub use self::color::ImColor32;
 pub use self::context::*;
 #[cfg(feature = "docking")]
+pub use self::dock_space::*;
+#[cfg(feature = "docking")]
 pub use self::docking_utils::*;
 pub use self::drag_drop::{DragDropFlags, DragDropSource, DragDropTarget};
 pub use self::draw_list::{ChannelsSplit, DrawListMut};
@@ -101,21 +103,30 @@ pub use self::fonts::atlas::*;
 pub use self::fonts::font::*;
 pub use self::fonts::glyph::*;
 pub use self::fonts::glyph_ranges::*;
+pub use self::fonts::icon::*;
+#[cfg(feature = "system-fonts")]
+pub use self::fonts::system::*;
 pub use self::input::keyboard::*;
 pub use self::input::mouse::*;
 pub use self::input_widget::*;
 pub use self::io::*;
 pub use self::layout::*;
 pub use self::list_clipper::ListClipper;
+pub use self::multi_select::{
+    MultiSelect, MultiSelectFlags, MultiSelectIo, SelectionRequest, SelectionStorage,
+};
 pub use self::platform_io::*;
 pub use self::plothistogram::PlotHistogram;
 pub use self::plotlines::PlotLines;
 pub use self::popups::*;
 pub use self::render::draw_data::*;
 pub use self::render::renderer::*;
+pub use self::settings::*;
 pub use self::stacks::*;
+pub use self::storage::*;
 pub use self::string::*;
 pub use self::style::*;
+pub use self::style_override::*;
 
 #[cfg(feature = "tables-api")]
 pub use self::tables::*;
@@ -123,6 +134,8 @@ pub use self::text_filter::*;
 pub use self::utils::*;
 pub use self::widget::color_editors::*;
 pub use self::widget::combo_box::*;
+#[cfg(feature = "docking")]
+pub use self::widget::custom::*;
 pub use self::widget::drag::*;
 pub use self::widget::image::*;
 pub use self::widget::list_box::*;
@@ -144,6 +157,8 @@ mod string;
 #[macro_use]
 mod tokens;
 
+#[cfg(feature = "automation")]
+pub mod automation;
 mod clipboard;
 pub mod color;
 mod columns;
@@ -153,6 +168,7 @@ mod dock_space;
 #[cfg(feature = "docking")]
 mod docking_utils;
 
+mod dpi;
 pub mod drag_drop;
 pub mod draw_list;
 mod fonts;
@@ -163,17 +179,26 @@ mod io;
 mod layout;
 mod list_clipper;
 mod math;
+mod multi_select;
 mod platform_io;
 mod plothistogram;
 mod plotlines;
 mod popups;
-mod render;
+pub mod render;
+#[cfg(feature = "remote")]
+pub mod remote;
+mod settings;
 mod stacks;
+mod storage;
 mod style;
+mod style_override;
+#[cfg(feature = "serde")]
+mod style_serde;
 #[cfg(feature = "tables-api")]
 mod tables;
 #[cfg(test)]
 mod test;
+pub mod testing;
 pub mod text_filter;
 mod utils;
 mod widget;
@@ -1031,6 +1056,7 @@ pub enum Condition {
 /// A cardinal direction
 #[repr(i32)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     None = sys::ImGuiDir_None,
     Left = sys::ImGuiDir_Left,
@@ -117,6 +117,26 @@ impl<'ui> ListClipperToken<'ui> {
         unsafe { (*self.list_clipper).DisplayEnd }
     }
 
+    /// Forces an item to be displayed even if it is not visible, e.g. the range source
+    /// item of a [multi-select scope](crate::MultiSelectIo::range_src_item).
+    ///
+    /// Must be called before the first call to `step`.
+    #[doc(alias = "IncludeItemByIndex")]
+    pub fn include_item_by_index(&mut self, item_index: i32) {
+        unsafe { sys::ImGuiListClipper_IncludeItemByIndex(self.list_clipper, item_index) }
+    }
+
+    /// Forces the items in `item_begin..item_end` to be displayed even if they are not
+    /// visible.
+    ///
+    /// Must be called before the first call to `step`.
+    #[doc(alias = "IncludeItemsByIndex")]
+    pub fn include_items_by_index(&mut self, item_begin: i32, item_end: i32) {
+        unsafe {
+            sys::ImGuiListClipper_IncludeItemsByIndex(self.list_clipper, item_begin, item_end)
+        }
+    }
+
     /// Get an iterator which outputs all visible indexes. This is the
     /// recommended way of using the clipper.
     pub fn iter(self) -> ListClipperIterator<'ui> {
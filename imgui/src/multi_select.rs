@@ -0,0 +1,463 @@
+//! Multi-selection of items, with shift-click range selection, ctrl-click toggling
+//! and box-selection.
+//!
+//! A multi-select scope is started with [`Ui::begin_multi_select`]. Every item in the
+//! scope is identified by its index, passed with [`Ui::set_next_item_selection_user_data`]
+//! right before the item (usually a [`Selectable`](crate::Selectable)) is submitted.
+//! Dear ImGui doesn't store the selection itself: instead, it emits requests at the
+//! beginning and at the end of the scope, which are applied to a [`SelectionStorage`].
+//!
+//! ```no_run
+//! # use imgui::*;
+//! struct Asset {
+//!     id: u64,
+//!     name: String,
+//! }
+//!
+//! fn asset_list(ui: &Ui, assets: &[Asset], selection: &mut SelectionStorage<u64>) {
+//!     let ms = ui.begin_multi_select(
+//!         MultiSelectFlags::CLEAR_ON_ESCAPE | MultiSelectFlags::BOX_SELECT_1D,
+//!         selection.len(),
+//!         assets.len(),
+//!     );
+//!     selection.apply_requests(ms.io(), |idx| assets[idx].id);
+//!
+//!     for (idx, asset) in assets.iter().enumerate() {
+//!         ui.set_next_item_selection_user_data(idx);
+//!         ui.selectable_config(&asset.name)
+//!             .selected(selection.contains(&asset.id))
+//!             .build();
+//!     }
+//!
+//!     let io = ms.end();
+//!     selection.apply_requests(&io, |idx| assets[idx].id);
+//! }
+//! ```
+
+use bitflags::bitflags;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::slice;
+
+use crate::sys;
+use crate::Ui;
+
+bitflags! {
+    /// Flags for [`Ui::begin_multi_select`]
+    #[repr(transparent)]
+    pub struct MultiSelectFlags: u32 {
+        /// Disable selecting more than one item. This is available to allow single-selection
+        /// code to share the same code as multi-selection.
+        const SINGLE_SELECT = sys::ImGuiMultiSelectFlags_SingleSelect;
+        /// Disable CTRL+A shortcut to select all.
+        const NO_SELECT_ALL = sys::ImGuiMultiSelectFlags_NoSelectAll;
+        /// Disable Shift+selection mouse/keyboard support (useful for unordered 2D selection).
+        const NO_RANGE_SELECT = sys::ImGuiMultiSelectFlags_NoRangeSelect;
+        /// Disable selecting items when navigating.
+        const NO_AUTO_SELECT = sys::ImGuiMultiSelectFlags_NoAutoSelect;
+        /// Disable clearing selection when navigating or selecting another one.
+        const NO_AUTO_CLEAR = sys::ImGuiMultiSelectFlags_NoAutoClear;
+        /// Disable clearing selection when clicking/selecting an already selected item.
+        const NO_AUTO_CLEAR_ON_RESELECT = sys::ImGuiMultiSelectFlags_NoAutoClearOnReselect;
+        /// Enable box-selection with same width and same x pos items (e.g. full row
+        /// selectables).
+        const BOX_SELECT_1D = sys::ImGuiMultiSelectFlags_BoxSelect1d;
+        /// Enable box-selection with varying width or varying x pos items support.
+        const BOX_SELECT_2D = sys::ImGuiMultiSelectFlags_BoxSelect2d;
+        /// Disable scrolling when box-selecting near edges of scope.
+        const BOX_SELECT_NO_SCROLL = sys::ImGuiMultiSelectFlags_BoxSelectNoScroll;
+        /// Clear selection when pressing Escape while scope is focused.
+        const CLEAR_ON_ESCAPE = sys::ImGuiMultiSelectFlags_ClearOnEscape;
+        /// Clear selection when clicking on empty location within scope.
+        const CLEAR_ON_CLICK_VOID = sys::ImGuiMultiSelectFlags_ClearOnClickVoid;
+        /// Scope for box-select and clear-on-click-void is the whole window. This is the
+        /// default.
+        const SCOPE_WINDOW = sys::ImGuiMultiSelectFlags_ScopeWindow;
+        /// Scope for box-select and clear-on-click-void is rectangle encompassing the
+        /// `begin_multi_select`/`end` scope.
+        const SCOPE_RECT = sys::ImGuiMultiSelectFlags_ScopeRect;
+        /// Apply selection on mouse down when clicking on an unselected item. This is the
+        /// default.
+        const SELECT_ON_CLICK = sys::ImGuiMultiSelectFlags_SelectOnClick;
+        /// Apply selection on mouse release when clicking an unselected item. Allows
+        /// dragging an unselected item without altering selection.
+        const SELECT_ON_CLICK_RELEASE = sys::ImGuiMultiSelectFlags_SelectOnClickRelease;
+        /// Wrap keyboard navigation on the X axis.
+        const NAV_WRAP_X = sys::ImGuiMultiSelectFlags_NavWrapX;
+    }
+}
+
+/// A selection request emitted by Dear ImGui in a multi-select scope.
+///
+/// You usually don't need to handle these yourself: [`SelectionStorage::apply_requests`]
+/// applies them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectionRequest {
+    /// Select all items (if `selected` is true) or clear the selection (if it is false).
+    SetAll { selected: bool },
+    /// Select or unselect the items with indices `first..=last`.
+    SetRange {
+        first: usize,
+        last: usize,
+        selected: bool,
+        /// `1` when `first` comes before `last` in the order the user selected them,
+        /// `-1` otherwise.
+        direction: i8,
+    },
+}
+
+/// Copy of the state of a multi-select scope, returned by [`MultiSelect::io`] and
+/// [`MultiSelect::end`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MultiSelectIo {
+    /// Requests to apply to the selection.
+    pub requests: Vec<SelectionRequest>,
+    /// Index of the item that starts a range selection, if any. When using a
+    /// [`ListClipper`](crate::ListClipper), this item must not be clipped.
+    pub range_src_item: Option<usize>,
+    /// Index of the item that has the navigation cursor, if any.
+    pub nav_id_item: Option<usize>,
+    /// True if the item with the navigation cursor is selected.
+    pub nav_id_selected: bool,
+    /// The number of items, as given to [`Ui::begin_multi_select`], or `None` if unknown.
+    pub items_count: Option<usize>,
+}
+
+fn selection_index(user_data: sys::ImGuiSelectionUserData) -> Option<usize> {
+    usize::try_from(user_data).ok()
+}
+
+impl MultiSelectIo {
+    fn from_raw(raw: &sys::ImGuiMultiSelectIO) -> Self {
+        let requests: &[sys::ImGuiSelectionRequest] = if raw.Requests.Size == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(raw.Requests.Data, raw.Requests.Size as usize) }
+        };
+        let requests = requests
+            .iter()
+            .filter_map(|request| match request.Type {
+                sys::ImGuiSelectionRequestType_SetAll => Some(SelectionRequest::SetAll {
+                    selected: request.Selected,
+                }),
+                sys::ImGuiSelectionRequestType_SetRange => Some(SelectionRequest::SetRange {
+                    first: selection_index(request.RangeFirstItem)?,
+                    last: selection_index(request.RangeLastItem)?,
+                    selected: request.Selected,
+                    direction: request.RangeDirection,
+                }),
+                _ => None,
+            })
+            .collect();
+
+        MultiSelectIo {
+            requests,
+            range_src_item: selection_index(raw.RangeSrcItem),
+            nav_id_item: selection_index(raw.NavIdItem),
+            nav_id_selected: raw.NavIdSelected,
+            items_count: usize::try_from(raw.ItemsCount).ok(),
+        }
+    }
+}
+
+/// Tracks a multi-select scope that can be ended by calling `.end()` or by dropping.
+///
+/// Created with [`Ui::begin_multi_select`].
+#[must_use]
+pub struct MultiSelect<'ui> {
+    io: MultiSelectIo,
+    ended: bool,
+    _phantom: PhantomData<&'ui Ui>,
+}
+
+impl<'ui> MultiSelect<'ui> {
+    /// Returns the state at the beginning of the scope.
+    ///
+    /// Its requests must be applied to the selection before submitting the items.
+    pub fn io(&self) -> &MultiSelectIo {
+        &self.io
+    }
+
+    /// Ends the multi-select scope, returning the requests made while submitting the items.
+    #[doc(alias = "EndMultiSelect")]
+    pub fn end(mut self) -> MultiSelectIo {
+        self.ended = true;
+        unsafe { MultiSelectIo::from_raw(&*sys::igEndMultiSelect()) }
+    }
+}
+
+impl Drop for MultiSelect<'_> {
+    fn drop(&mut self) {
+        if !self.ended {
+            unsafe {
+                sys::igEndMultiSelect();
+            }
+        }
+    }
+}
+
+impl Ui {
+    /// Begins a multi-select scope.
+    ///
+    /// `selection_size` is the number of currently selected items, and `items_count` the
+    /// total number of items in the scope. Both are used to enable some features, such as
+    /// select-all with CTRL+A or box-selection.
+    #[doc(alias = "BeginMultiSelect")]
+    pub fn begin_multi_select(
+        &self,
+        flags: MultiSelectFlags,
+        selection_size: usize,
+        items_count: usize,
+    ) -> MultiSelect<'_> {
+        let io = unsafe {
+            &*sys::igBeginMultiSelect(
+                flags.bits() as i32,
+                selection_size as i32,
+                items_count as i32,
+            )
+        };
+        MultiSelect {
+            io: MultiSelectIo::from_raw(io),
+            ended: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the index of the next item within the current multi-select scope.
+    ///
+    /// Must be called before submitting each selectable item of the scope.
+    #[doc(alias = "SetNextItemSelectionUserData")]
+    pub fn set_next_item_selection_user_data(&self, index: usize) {
+        unsafe { sys::igSetNextItemSelectionUserData(index as sys::ImGuiSelectionUserData) }
+    }
+
+    /// Returns true if the last item was toggled by a multi-select request this frame.
+    #[doc(alias = "IsItemToggledSelection")]
+    pub fn is_item_toggled_selection(&self) -> bool {
+        unsafe { sys::igIsItemToggledSelection() }
+    }
+}
+
+/// A set of selected items, identified by a key of type `K`.
+///
+/// Items in a multi-select scope are identified by their index, so applying requests
+/// takes an adapter mapping an index to its key. Keys should be stable identifiers
+/// (e.g. database ids) rather than indices, so that the selection survives sorting
+/// and filtering.
+#[derive(Clone, Debug)]
+pub struct SelectionStorage<K> {
+    selected: HashSet<K>,
+}
+
+/// We manually impl Default as `#[derive(Default)]`
+/// incorrectly requires `K: Default`.
+impl<K> Default for SelectionStorage<K> {
+    fn default() -> Self {
+        Self {
+            selected: HashSet::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq> SelectionStorage<K> {
+    /// Creates an empty selection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of selected items.
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    /// Returns true if no item is selected.
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Returns true if the item is selected.
+    pub fn contains(&self, key: &K) -> bool {
+        self.selected.contains(key)
+    }
+
+    /// Selects or unselects an item.
+    pub fn set_selected(&mut self, key: K, selected: bool) {
+        if selected {
+            self.selected.insert(key);
+        } else {
+            self.selected.remove(&key);
+        }
+    }
+
+    /// Unselects all items.
+    pub fn clear(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Returns an iterator over the selected items, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &K> {
+        self.selected.iter()
+    }
+
+    /// Applies the requests of a multi-select scope to this selection.
+    ///
+    /// `adapter` maps the index of an item in the scope to its key. Selecting all items
+    /// requires the items count to have been passed to [`Ui::begin_multi_select`].
+    pub fn apply_requests<F>(&mut self, io: &MultiSelectIo, mut adapter: F)
+    where
+        F: FnMut(usize) -> K,
+    {
+        for request in &io.requests {
+            match *request {
+                SelectionRequest::SetAll { selected } => {
+                    self.selected.clear();
+                    if selected {
+                        let items_count = io.items_count.unwrap_or(0);
+                        self.selected.extend((0..items_count).map(&mut adapter));
+                    }
+                }
+                SelectionRequest::SetRange {
+                    first,
+                    last,
+                    selected,
+                    ..
+                } => {
+                    for idx in first.min(last)..=first.max(last) {
+                        self.set_selected(adapter(idx), selected);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<K> IntoIterator for SelectionStorage<K> {
+    type Item = K;
+    type IntoIter = std::collections::hash_set::IntoIter<K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.selected.into_iter()
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for SelectionStorage<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        Self {
+            selected: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[test]
+fn test_selection_storage_apply_requests() {
+    let keys = ["a", "b", "c", "d", "e"];
+    let mut selection = SelectionStorage::new();
+
+    let io = MultiSelectIo {
+        requests: vec![
+            SelectionRequest::SetAll { selected: false },
+            SelectionRequest::SetRange {
+                first: 3,
+                last: 1,
+                selected: true,
+                direction: -1,
+            },
+        ],
+        items_count: Some(keys.len()),
+        ..Default::default()
+    };
+    selection.apply_requests(&io, |idx| keys[idx]);
+    assert_eq!(selection.len(), 3);
+    assert!(selection.contains(&"b") && selection.contains(&"c") && selection.contains(&"d"));
+
+    let io = MultiSelectIo {
+        requests: vec![SelectionRequest::SetRange {
+            first: 2,
+            last: 2,
+            selected: false,
+            direction: 1,
+        }],
+        ..io
+    };
+    selection.apply_requests(&io, |idx| keys[idx]);
+    assert!(!selection.contains(&"c"));
+    assert_eq!(selection.len(), 2);
+
+    let io = MultiSelectIo {
+        requests: vec![SelectionRequest::SetAll { selected: true }],
+        ..io
+    };
+    selection.apply_requests(&io, |idx| keys[idx]);
+    assert_eq!(selection.len(), keys.len());
+}
+
+#[test]
+fn test_multi_select_scope() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let mut selection: SelectionStorage<u32> = (0..4).collect();
+
+    let _window = ui.window("Example").begin();
+    let ms = ui.begin_multi_select(MultiSelectFlags::empty(), selection.len(), 10);
+    assert!(ms.io().requests.is_empty());
+    assert_eq!(ms.io().items_count, Some(10));
+    selection.apply_requests(ms.io(), |idx| idx as u32);
+
+    for idx in 0..10 {
+        ui.set_next_item_selection_user_data(idx);
+        ui.selectable_config(format!("Item {}", idx))
+            .selected(selection.contains(&(idx as u32)))
+            .build();
+    }
+
+    let io = ms.end();
+    assert!(io.requests.is_empty());
+    assert_eq!(selection.len(), 4);
+}
+
+#[test]
+fn test_multi_select_click() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut selection: SelectionStorage<usize> = (0..4).collect();
+    let mut click_pos = [0.0, 0.0];
+
+    for frame in 0..3 {
+        match frame {
+            1 => {
+                ctx.io_mut().add_mouse_pos_event(click_pos);
+                ctx.io_mut()
+                    .add_mouse_button_event(crate::MouseButton::Left, true);
+            }
+            2 => ctx
+                .io_mut()
+                .add_mouse_button_event(crate::MouseButton::Left, false),
+            _ => {}
+        }
+
+        let ui = ctx.new_frame();
+        ui.window("Example")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([200.0, 400.0], crate::Condition::Always)
+            .build(|| {
+                let ms = ui.begin_multi_select(MultiSelectFlags::empty(), selection.len(), 10);
+                selection.apply_requests(ms.io(), |idx| idx);
+                for idx in 0..10 {
+                    ui.set_next_item_selection_user_data(idx);
+                    ui.selectable_config(format!("Item {}", idx))
+                        .selected(selection.contains(&idx))
+                        .build();
+                    if idx == 6 {
+                        let [min_x, min_y] = ui.item_rect_min();
+                        click_pos = [min_x + 5.0, min_y + 5.0];
+                    }
+                }
+                let io = ms.end();
+                selection.apply_requests(&io, |idx| idx);
+            });
+        ctx.render();
+    }
+
+    assert_eq!(selection.len(), 1);
+    assert!(selection.contains(&6));
+}
@@ -0,0 +1,837 @@
+//! Streaming a UI to a remote viewer over a socket.
+//!
+//! A [`RemoteServer`] runs next to the application, for example on a headless machine. Each frame
+//! it sends the [`DrawData`] to the connected [`RemoteClient`], along with font atlas texture
+//! updates, and it feeds the input events it receives back into [`Io`](crate::Io). The client
+//! keeps the latest frame, which it can draw with any renderer through
+//! [`CapturedFrame::replay`].
+//!
+//! Only the draw lists that changed since the previous frame are sent. Both TCP and Unix domain
+//! sockets are supported, and a single client is served at a time: a new connection replaces the
+//! previous one.
+//!
+//! The protocol has no authentication or encryption, and the input events of the viewer are
+//! applied to the context as they are. Never expose the server to an untrusted network: bind it
+//! to a loopback address or a Unix domain socket, and use e.g. an SSH tunnel to reach it from
+//! another machine.
+//!
+//! ```no_run
+//! # use imgui::remote::RemoteServer;
+//! # let mut ctx = imgui::Context::create();
+//! let mut server = RemoteServer::bind_tcp("127.0.0.1:8888").unwrap();
+//! loop {
+//!     server.poll(&mut ctx).unwrap();
+//!     let ui = ctx.new_frame();
+//!     ui.text("Hello from the server");
+//!     server.send_frame(ctx.render()).unwrap();
+//! }
+//! ```
+//!
+//! # Protocol
+//!
+//! Every message is a `u8` tag followed by a little-endian `u32` payload length and the payload.
+//! The server starts with a hello message carrying the protocol version, then sends texture and
+//! frame messages. Frames use the list encoding of [`render::capture`](crate::render::capture),
+//! prefixed with a flag telling whether each list is unchanged from the previous frame. The client
+//! sends input events and its display size.
+
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::render::capture::{
+    write_f32s, write_len, CaptureError, CapturedDrawList, CapturedFrame, Reader,
+};
+use crate::render::draw_data::DrawData;
+use crate::render::renderer::{TextureId, Textures};
+use crate::render::software::RgbaImage;
+use crate::testing::InputEvent;
+use crate::{Context, Key, MouseButton};
+
+const MAGIC: [u8; 4] = *b"IMRS";
+const VERSION: u16 = 1;
+const MAX_MESSAGE_LEN: usize = 1 << 28;
+/// Outgoing data a viewer may fall behind by before it is disconnected.
+const MAX_QUEUED_LEN: usize = 1 << 28;
+
+const MSG_HELLO: u8 = 1;
+const MSG_TEXTURE: u8 = 2;
+const MSG_FRAME: u8 = 3;
+const MSG_INPUT: u8 = 16;
+const MSG_DISPLAY_SIZE: u8 = 17;
+
+const LIST_UNCHANGED: u8 = 0;
+const LIST_FULL: u8 = 1;
+
+/// The application side of a remote UI connection.
+#[derive(Debug)]
+pub struct RemoteServer {
+    listener: Listener,
+    connection: Option<Connection>,
+    last_frame: Option<CapturedFrame>,
+}
+
+impl RemoteServer {
+    /// Listens for a viewer on a TCP address.
+    pub fn bind_tcp(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::new(Listener::Tcp(listener)))
+    }
+
+    /// Listens for a viewer on a Unix domain socket created at `path`.
+    #[cfg(unix)]
+    pub fn bind_unix(path: impl AsRef<Path>) -> io::Result<Self> {
+        let listener = UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::new(Listener::Unix(listener)))
+    }
+
+    fn new(listener: Listener) -> Self {
+        RemoteServer {
+            listener,
+            connection: None,
+            last_frame: None,
+        }
+    }
+
+    /// Returns the address a TCP server listens on, which is useful after binding port 0.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match &self.listener {
+            Listener::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Listener::Unix(_) => None,
+        }
+    }
+
+    /// Returns `true` if a viewer is connected.
+    pub fn is_connected(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// Accepts a pending viewer connection, applies the input events it sent to the context, and
+    /// sends as much of the queued data as the viewer accepts.
+    ///
+    /// This never blocks, and should be called once per frame before [`Context::new_frame`]. A
+    /// newly connected viewer receives the font atlas texture.
+    ///
+    /// A viewer that sends an invalid message is disconnected, and the error is returned. The
+    /// server then waits for a new viewer.
+    pub fn poll(&mut self, ctx: &mut Context) -> io::Result<()> {
+        if let Some(stream) = self.listener.accept()? {
+            self.connection = Some(Connection::new(stream)?);
+            self.last_frame = None;
+            let mut hello = MAGIC.to_vec();
+            hello.extend_from_slice(&VERSION.to_le_bytes());
+            self.send(MSG_HELLO, &hello)?;
+            self.send_font_texture(ctx)?;
+        }
+        let connection = match &mut self.connection {
+            Some(connection) => connection,
+            None => return Ok(()),
+        };
+        let result = connection
+            .flush()
+            .and_then(|()| connection.receive())
+            .and_then(|open| {
+                while let Some((tag, payload)) = connection.next_message()? {
+                    let mut r = Reader(payload.as_slice());
+                    match tag {
+                        MSG_INPUT => read_input_event(&mut r)?.apply(ctx),
+                        MSG_DISPLAY_SIZE => {
+                            ctx.io_mut().display_size = r.f32s().map_err(invalid_data)?
+                        }
+                        _ => return Err(protocol_error("unexpected message from client")),
+                    }
+                }
+                Ok(open)
+            });
+        match result {
+            Ok(true) => Ok(()),
+            Ok(false) => {
+                self.connection = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.connection = None;
+                if is_disconnect(&e) {
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    /// Sends the font atlas texture of the context to the viewer.
+    ///
+    /// This is done automatically when a viewer connects, and must be repeated whenever the
+    /// atlas is rebuilt.
+    pub fn send_font_texture(&mut self, ctx: &mut Context) -> io::Result<()> {
+        if self.connection.is_none() {
+            return Ok(());
+        }
+        let fonts = ctx.fonts();
+        let id = fonts.tex_id;
+        let image = RgbaImage::from_font_atlas_texture(&fonts.build_rgba32_texture());
+        self.send_texture(id, &image)
+    }
+
+    /// Sends a texture used by the draw data, replacing any texture with the same id on the
+    /// viewer.
+    pub fn send_texture(&mut self, id: TextureId, image: &RgbaImage) -> io::Result<()> {
+        let mut payload = Vec::with_capacity(16 + image.pixels().len());
+        payload.extend_from_slice(&(id.id() as u64).to_le_bytes());
+        payload.extend_from_slice(&image.width().to_le_bytes());
+        payload.extend_from_slice(&image.height().to_le_bytes());
+        payload.extend_from_slice(image.pixels());
+        self.send(MSG_TEXTURE, &payload)
+    }
+
+    /// Sends the draw data of a frame to the viewer, if one is connected.
+    ///
+    /// This never blocks. The frame is dropped while the viewer has not received all the data
+    /// sent before, so a slow viewer shows fewer frames instead of stalling the application.
+    pub fn send_frame(&mut self, draw_data: &DrawData) -> io::Result<()> {
+        match &self.connection {
+            Some(connection) if connection.queued_len() == 0 => (),
+            _ => return Ok(()),
+        }
+        let frame = CapturedFrame::new(draw_data);
+        let payload = encode_frame(self.last_frame.as_ref(), &frame);
+        self.send(MSG_FRAME, &payload)?;
+        if self.connection.is_some() {
+            self.last_frame = Some(frame);
+        }
+        Ok(())
+    }
+
+    /// Queues a message, disconnecting the viewer if it fell too far behind or the connection
+    /// failed.
+    fn send(&mut self, tag: u8, payload: &[u8]) -> io::Result<()> {
+        if let Some(connection) = &mut self.connection {
+            let result = connection.send(tag, payload).and_then(|()| {
+                if connection.queued_len() > MAX_QUEUED_LEN {
+                    Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "viewer is not receiving data",
+                    ))
+                } else {
+                    Ok(())
+                }
+            });
+            if let Err(e) = result {
+                self.connection = None;
+                if !is_disconnect(&e) {
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The viewer side of a remote UI connection.
+#[derive(Debug)]
+pub struct RemoteClient {
+    connection: Connection,
+    handshake_done: bool,
+    frame: Option<CapturedFrame>,
+    frame_count: u64,
+    textures: Textures<RgbaImage>,
+}
+
+impl RemoteClient {
+    /// Connects to a server listening on a TCP address.
+    pub fn connect_tcp(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        Self::new(Stream::Tcp(stream))
+    }
+
+    /// Connects to a server listening on a Unix domain socket.
+    #[cfg(unix)]
+    pub fn connect_unix(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(Stream::Unix(UnixStream::connect(path)?))
+    }
+
+    fn new(stream: Stream) -> io::Result<Self> {
+        Ok(RemoteClient {
+            connection: Connection::new(stream)?,
+            handshake_done: false,
+            frame: None,
+            frame_count: 0,
+            textures: Textures::new(),
+        })
+    }
+
+    /// Sends an input event to be applied to the server's context.
+    pub fn send_input(&mut self, event: InputEvent) -> io::Result<()> {
+        let mut payload = Vec::new();
+        write_input_event(&mut payload, event);
+        self.connection.send(MSG_INPUT, &payload)
+    }
+
+    /// Sets the display size of the server's context.
+    pub fn set_display_size(&mut self, size: [f32; 2]) -> io::Result<()> {
+        let mut payload = Vec::new();
+        write_f32s(&mut payload, &size)?;
+        self.connection.send(MSG_DISPLAY_SIZE, &payload)
+    }
+
+    /// Processes the messages received from the server without blocking.
+    ///
+    /// Returns `true` if at least one new frame was received. Fails with
+    /// [`io::ErrorKind::UnexpectedEof`] once the server closed the connection.
+    pub fn poll(&mut self) -> io::Result<bool> {
+        self.connection.flush()?;
+        let open = self.connection.receive()?;
+        let mut new_frame = false;
+        while let Some((tag, payload)) = self.connection.next_message()? {
+            let mut r = Reader(payload.as_slice());
+            if !self.handshake_done {
+                if tag != MSG_HELLO || r.array().map_err(invalid_data)? != MAGIC {
+                    return Err(protocol_error("not a remote UI server"));
+                }
+                let version = r.u16().map_err(invalid_data)?;
+                if version != VERSION {
+                    return Err(protocol_error("unsupported protocol version"));
+                }
+                self.handshake_done = true;
+                continue;
+            }
+            match tag {
+                MSG_TEXTURE => {
+                    let id = r.u64().map_err(invalid_data)?;
+                    let width = u32::from_le_bytes(r.array().map_err(invalid_data)?);
+                    let height = u32::from_le_bytes(r.array().map_err(invalid_data)?);
+                    let image = RgbaImage::from_raw(width, height, r.0.to_vec())
+                        .ok_or_else(|| protocol_error("invalid texture size"))?;
+                    self.textures.replace(TextureId::new(id as usize), image);
+                }
+                MSG_FRAME => {
+                    let frame = decode_frame(self.frame.as_ref(), &mut r).map_err(invalid_data)?;
+                    self.frame = Some(frame);
+                    self.frame_count += 1;
+                    new_frame = true;
+                }
+                _ => return Err(protocol_error("unexpected message from server")),
+            }
+        }
+        if !open {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(new_frame)
+    }
+
+    /// Processes messages until a new frame is received or the timeout expires.
+    ///
+    /// Returns `false` on timeout.
+    pub fn wait_frame(&mut self, timeout: Duration) -> io::Result<bool> {
+        let start = Instant::now();
+        loop {
+            if self.poll()? {
+                return Ok(true);
+            }
+            if start.elapsed() >= timeout {
+                return Ok(false);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    /// Returns the latest frame received from the server.
+    pub fn frame(&self) -> Option<&CapturedFrame> {
+        self.frame.as_ref()
+    }
+
+    /// Returns the number of frames received so far.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Returns the textures received from the server, keyed by the server's texture ids.
+    pub fn textures(&self) -> &Textures<RgbaImage> {
+        &self.textures
+    }
+}
+
+fn encode_frame(previous: Option<&CapturedFrame>, frame: &CapturedFrame) -> Vec<u8> {
+    let mut payload = Vec::new();
+    let textures = frame.texture_ids();
+    let write = |w: &mut Vec<u8>| -> io::Result<()> {
+        for v in [
+            frame.display_pos,
+            frame.display_size,
+            frame.framebuffer_scale,
+        ] {
+            write_f32s(w, &v)?;
+        }
+        write_len(w, textures.len())?;
+        for id in &textures {
+            w.extend_from_slice(&(id.id() as u64).to_le_bytes());
+        }
+        write_len(w, frame.lists.len())?;
+        for (i, list) in frame.lists.iter().enumerate() {
+            if previous.and_then(|p| p.lists.get(i)) == Some(list) {
+                w.push(LIST_UNCHANGED);
+            } else {
+                w.push(LIST_FULL);
+                list.write_to(w, &textures)?;
+            }
+        }
+        Ok(())
+    };
+    write(&mut payload).expect("writing to a Vec cannot fail");
+    payload
+}
+
+fn decode_frame(
+    previous: Option<&CapturedFrame>,
+    r: &mut Reader<&[u8]>,
+) -> Result<CapturedFrame, CaptureError> {
+    let display_pos = r.f32s()?;
+    let display_size = r.f32s()?;
+    let framebuffer_scale = r.f32s()?;
+    let mut textures = Vec::new();
+    for _ in 0..r.len()? {
+        textures.push(TextureId::new(r.u64()? as usize));
+    }
+    let list_count = r.len()?;
+    let mut lists = Vec::new();
+    for i in 0..list_count {
+        let list = match r.u8()? {
+            LIST_UNCHANGED => {
+                previous
+                    .and_then(|p| p.lists.get(i))
+                    .cloned()
+                    .ok_or(CaptureError::Corrupt(
+                        "unchanged list without a previous frame",
+                    ))?
+            }
+            LIST_FULL => CapturedDrawList::read_from(r, &textures)?,
+            _ => return Err(CaptureError::Corrupt("unknown list encoding")),
+        };
+        lists.push(list);
+    }
+    Ok(CapturedFrame {
+        display_pos,
+        display_size,
+        framebuffer_scale,
+        lists,
+    })
+}
+
+fn write_input_event(w: &mut Vec<u8>, event: InputEvent) {
+    let f32s = |w: &mut Vec<u8>, v: [f32; 2]| {
+        w.extend_from_slice(&v[0].to_le_bytes());
+        w.extend_from_slice(&v[1].to_le_bytes());
+    };
+    match event {
+        InputEvent::MousePos(pos) => {
+            w.push(0);
+            f32s(w, pos);
+        }
+        InputEvent::MouseButton(button, down) => {
+            w.extend_from_slice(&[1, button as u8, down as u8])
+        }
+        InputEvent::MouseWheel(wheel) => {
+            w.push(2);
+            f32s(w, wheel);
+        }
+        InputEvent::Key(key, down) => {
+            w.push(3);
+            w.extend_from_slice(&(key as u32).to_le_bytes());
+            w.push(down as u8);
+        }
+        InputEvent::Char(c) => {
+            w.push(4);
+            w.extend_from_slice(&(c as u32).to_le_bytes());
+        }
+        InputEvent::Focus(focused) => w.extend_from_slice(&[5, focused as u8]),
+    }
+}
+
+fn read_input_event(r: &mut Reader<&[u8]>) -> io::Result<InputEvent> {
+    let invalid = || protocol_error("invalid input event");
+    let event = match r.u8().map_err(invalid_data)? {
+        0 => InputEvent::MousePos(r.f32s().map_err(invalid_data)?),
+        1 => {
+            let button = *MouseButton::VARIANTS
+                .get(r.u8().map_err(invalid_data)? as usize)
+                .ok_or_else(invalid)?;
+            InputEvent::MouseButton(button, r.u8().map_err(invalid_data)? != 0)
+        }
+        2 => InputEvent::MouseWheel(r.f32s().map_err(invalid_data)?),
+        3 => {
+            let raw = u32::from_le_bytes(r.array().map_err(invalid_data)?);
+            let key = *Key::VARIANTS
+                .iter()
+                .find(|&&key| key as u32 == raw)
+                .ok_or_else(invalid)?;
+            InputEvent::Key(key, r.u8().map_err(invalid_data)? != 0)
+        }
+        4 => {
+            let raw = u32::from_le_bytes(r.array().map_err(invalid_data)?);
+            InputEvent::Char(char::from_u32(raw).ok_or_else(invalid)?)
+        }
+        5 => InputEvent::Focus(r.u8().map_err(invalid_data)? != 0),
+        _ => return Err(invalid()),
+    };
+    Ok(event)
+}
+
+fn protocol_error(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn invalid_data(e: CaptureError) -> io::Error {
+    match e {
+        CaptureError::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, e),
+    }
+}
+
+fn is_disconnect(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::BrokenPipe
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+#[derive(Debug)]
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    fn accept(&self) -> io::Result<Option<Stream>> {
+        let result = match self {
+            Listener::Tcp(listener) => listener.accept().and_then(|(stream, _)| {
+                stream.set_nodelay(true)?;
+                Ok(Stream::Tcp(stream))
+            }),
+            #[cfg(unix)]
+            Listener::Unix(listener) => listener.accept().map(|(stream, _)| Stream::Unix(stream)),
+        };
+        match result {
+            Ok(stream) => Ok(Some(stream)),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.set_nonblocking(nonblocking),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.set_nonblocking(nonblocking),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Stream::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// A framed message stream that never blocks. Outgoing messages are queued and written as far
+/// as the socket accepts them, incoming data is drained.
+#[derive(Debug)]
+struct Connection {
+    stream: Stream,
+    incoming: Vec<u8>,
+    outgoing: Vec<u8>,
+}
+
+impl Connection {
+    fn new(stream: Stream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        Ok(Connection {
+            stream,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+        })
+    }
+
+    fn send(&mut self, tag: u8, payload: &[u8]) -> io::Result<()> {
+        self.outgoing.push(tag);
+        self.outgoing
+            .extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        self.outgoing.extend_from_slice(payload);
+        self.flush()
+    }
+
+    /// Returns the number of bytes queued but not yet written to the socket.
+    fn queued_len(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    /// Writes as much of the queued data as the socket accepts.
+    fn flush(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        let result = loop {
+            if written == self.outgoing.len() {
+                break Ok(());
+            }
+            match self.stream.write(&self.outgoing[written..]) {
+                Ok(0) => break Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => written += n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => break Err(e),
+            }
+        };
+        self.outgoing.drain(..written);
+        result
+    }
+
+    /// Reads all available data, returning `false` if the peer closed the connection.
+    fn receive(&mut self) -> io::Result<bool> {
+        let mut buf = [0; 16 * 1024];
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => return Ok(false),
+                Ok(n) => self.incoming.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(true),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn next_message(&mut self) -> io::Result<Option<(u8, Vec<u8>)>> {
+        if self.incoming.len() < 5 {
+            return Ok(None);
+        }
+        let len = u32::from_le_bytes(self.incoming[1..5].try_into().unwrap()) as usize;
+        if len > MAX_MESSAGE_LEN {
+            return Err(protocol_error("message too large"));
+        }
+        if self.incoming.len() < 5 + len {
+            return Ok(None);
+        }
+        let tag = self.incoming[0];
+        let payload = self.incoming[5..5 + len].to_vec();
+        self.incoming.drain(..5 + len);
+        Ok(Some((tag, payload)))
+    }
+}
+
+#[cfg(test)]
+fn run_loopback(mut server: RemoteServer, connect: impl FnOnce() -> io::Result<RemoteClient>) {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut client = connect().unwrap();
+    client.set_display_size([320.0, 200.0]).unwrap();
+    client.send_input(InputEvent::MousePos([5.0, 6.0])).unwrap();
+
+    let timeout = Instant::now() + Duration::from_secs(10);
+    while ctx.io().display_size != [320.0, 200.0] {
+        assert!(Instant::now() < timeout, "server did not receive input");
+        server.poll(&mut ctx).unwrap();
+    }
+    assert!(server.is_connected());
+
+    for frame in 0..3 {
+        let ui = ctx.new_frame();
+        if frame == 0 {
+            assert_eq!(ui.io().mouse_pos, [5.0, 6.0]);
+        }
+        ui.window("Remote").build(|| ui.text("Streamed"));
+        let draw_data = ctx.render();
+        server.send_frame(draw_data).unwrap();
+        assert!(client.wait_frame(Duration::from_secs(10)).unwrap());
+        assert_eq!(client.frame(), Some(&CapturedFrame::new(draw_data)));
+    }
+    assert_eq!(client.frame_count(), 3);
+    let font = client.textures().get(ctx.fonts().tex_id).unwrap();
+    assert_eq!(font.width(), ctx.fonts().build_rgba32_texture().width);
+
+    drop(server);
+    let timeout = Instant::now() + Duration::from_secs(10);
+    loop {
+        assert!(
+            Instant::now() < timeout,
+            "client did not notice the disconnection"
+        );
+        match client.poll() {
+            Ok(_) => std::thread::sleep(Duration::from_millis(1)),
+            Err(e) => {
+                assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+                break;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_remote_tcp_loopback() {
+    let server = RemoteServer::bind_tcp("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    run_loopback(server, || RemoteClient::connect_tcp(addr));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_remote_unix_loopback() {
+    let path = std::env::temp_dir().join(format!("imgui-remote-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let server = RemoteServer::bind_unix(&path).unwrap();
+    run_loopback(server, || RemoteClient::connect_unix(&path));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_remote_frame_delta() {
+    let list = |n: usize| CapturedDrawList {
+        vtx_buffer: vec![
+            crate::DrawVert {
+                pos: [0.0, 0.0],
+                uv: [0.0, 0.0],
+                col: [0; 4],
+            };
+            n
+        ],
+        ..Default::default()
+    };
+    let frame = |lists: Vec<CapturedDrawList>| CapturedFrame {
+        display_pos: [0.0, 0.0],
+        display_size: [100.0, 100.0],
+        framebuffer_scale: [1.0, 1.0],
+        lists,
+    };
+    let first = frame(vec![list(100), list(200)]);
+    let second = frame(vec![list(100), list(10)]);
+    let full = encode_frame(None, &second);
+    let delta = encode_frame(Some(&first), &second);
+    assert!(delta.len() < full.len() - 100 * 20);
+
+    let decoded = decode_frame(Some(&first), &mut Reader(delta.as_slice())).unwrap();
+    assert_eq!(decoded, second);
+    assert!(decode_frame(None, &mut Reader(delta.as_slice())).is_err());
+}
+
+#[test]
+fn test_remote_input_events() {
+    for event in [
+        InputEvent::MousePos([1.5, -2.0]),
+        InputEvent::MouseButton(MouseButton::Right, true),
+        InputEvent::MouseWheel([0.0, -1.0]),
+        InputEvent::Key(Key::Enter, false),
+        InputEvent::Char('é'),
+        InputEvent::Focus(true),
+    ] {
+        let mut payload = Vec::new();
+        write_input_event(&mut payload, event);
+        assert_eq!(
+            read_input_event(&mut Reader(payload.as_slice())).unwrap(),
+            event
+        );
+    }
+    assert!(read_input_event(&mut Reader(&[9][..])).is_err());
+}
+
+#[test]
+fn test_remote_invalid_message_disconnects() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut server = RemoteServer::bind_tcp("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    let timeout = Instant::now() + Duration::from_secs(10);
+
+    // An unknown message, and one longer than the limit
+    for message in [[42, 0, 0, 0, 0], [MSG_INPUT, 0xFF, 0xFF, 0xFF, 0xFF]] {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(&message).unwrap();
+        let error = loop {
+            assert!(
+                Instant::now() < timeout,
+                "server did not reject the message"
+            );
+            if let Err(e) = server.poll(&mut ctx) {
+                break e;
+            }
+        };
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(!server.is_connected());
+        server.poll(&mut ctx).unwrap();
+    }
+
+    let mut client = RemoteClient::connect_tcp(addr).unwrap();
+    client.set_display_size([64.0, 48.0]).unwrap();
+    while ctx.io().display_size != [64.0, 48.0] {
+        assert!(
+            Instant::now() < timeout,
+            "server did not accept a new viewer"
+        );
+        server.poll(&mut ctx).unwrap();
+    }
+    assert!(server.is_connected());
+}
+
+#[test]
+fn test_remote_slow_viewer() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut server = RemoteServer::bind_tcp("127.0.0.1:0").unwrap();
+    // A viewer that never reads
+    let _stream = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+    let timeout = Instant::now() + Duration::from_secs(10);
+    while !server.is_connected() {
+        assert!(Instant::now() < timeout, "server did not accept the viewer");
+        server.poll(&mut ctx).unwrap();
+    }
+
+    // More than the socket buffers hold, so the rest stays queued
+    let image = RgbaImage::new(2048, 2048);
+    server.send_texture(TextureId::new(1), &image).unwrap();
+    let queued = server.connection.as_ref().unwrap().queued_len();
+    assert!(queued > 0);
+    for _ in 0..3 {
+        server.poll(&mut ctx).unwrap();
+        ctx.new_frame().text("dropped");
+        server.send_frame(ctx.render()).unwrap();
+    }
+    assert!(server.is_connected());
+    assert!(server.last_frame.is_none());
+    assert!(server.connection.as_ref().unwrap().queued_len() <= queued);
+}
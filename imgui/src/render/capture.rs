@@ -0,0 +1,823 @@
+//! Recording [`DrawData`] to a compact binary file and replaying it.
+//!
+//! A [`DrawDataCapture`] holds a sequence of [`CapturedFrame`]s, each a full copy of the vertex,
+//! index and command buffers of one frame, together with the texture ids they reference. Captures
+//! can be written to and read from any [`Write`]/[`Read`] stream, and every frame can be turned
+//! back into a [`DrawData`] with [`CapturedFrame::replay`], which any renderer can draw without
+//! a Dear ImGui context. This is useful to attach the exact output of a frame to a bug report, to benchmark
+//! renderers offline, or to display a UI on another machine.
+//!
+//! ```no_run
+//! # use imgui::render::capture::DrawDataCapture;
+//! # let mut ctx = imgui::Context::create();
+//! let mut capture = DrawDataCapture::new();
+//! for _ in 0..3 {
+//!     let ui = ctx.new_frame();
+//!     ui.text("Hello");
+//!     capture.record(ctx.render());
+//! }
+//! capture.save("frames.imdc").unwrap();
+//!
+//! let capture = DrawDataCapture::load("frames.imdc").unwrap();
+//! for frame in capture.frames() {
+//!     let replay = frame.replay().unwrap();
+//!     let draw_data = replay.draw_data();
+//!     // renderer.render(draw_data)
+//! # let _ = draw_data;
+//! }
+//! ```
+//!
+//! # File format
+//!
+//! All values are little-endian. A file starts with the magic bytes `IMDC`, a `u16` version and
+//! a `u8` index size, followed by a table of the referenced texture ids (`u32` count, `u64` ids)
+//! and the frames (`u32` count). Commands refer to textures by their position in the table.
+//!
+//! User callbacks cannot be serialized, so [`DrawCmd::RawCallback`] commands are not recorded.
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::internal::RawCast;
+use crate::render::draw_data::{
+    DrawCmd, DrawCmdParams, DrawData, DrawIdx, DrawList, DrawVert, RESET_RENDER_STATE_CALLBACK,
+};
+use crate::render::renderer::TextureId;
+use crate::sys;
+
+const MAGIC: [u8; 4] = *b"IMDC";
+const VERSION: u16 = 1;
+
+const CMD_ELEMENTS: u8 = 0;
+const CMD_RESET_RENDER_STATE: u8 = 1;
+
+/// A recorded sequence of frames.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DrawDataCapture {
+    frames: Vec<CapturedFrame>,
+}
+
+/// An owned copy of the draw data of a single frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedFrame {
+    /// Upper-left position of the viewport
+    pub display_pos: [f32; 2],
+    /// Size of the viewport
+    pub display_size: [f32; 2],
+    /// Amount of pixels for each unit of display size
+    pub framebuffer_scale: [f32; 2],
+    /// The draw lists, in rendering order
+    pub lists: Vec<CapturedDrawList>,
+}
+
+/// An owned copy of a [`DrawList`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CapturedDrawList {
+    /// The vertices
+    pub vtx_buffer: Vec<DrawVert>,
+    /// The indices into `vtx_buffer`, relative to the vertex offset of each command
+    pub idx_buffer: Vec<DrawIdx>,
+    /// The commands, which draw ranges of `idx_buffer`
+    pub commands: Vec<CapturedDrawCmd>,
+}
+
+/// A recorded draw command.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CapturedDrawCmd {
+    /// [`DrawCmd::Elements`]
+    Elements {
+        /// The number of indices used for this draw command
+        count: usize,
+        /// The clip rectangle, texture and buffer offsets
+        cmd_params: DrawCmdParams,
+    },
+    /// [`DrawCmd::ResetRenderState`]
+    ResetRenderState,
+}
+
+/// An error while reading a capture.
+#[derive(Debug)]
+pub enum CaptureError {
+    /// The underlying stream failed
+    Io(io::Error),
+    /// The data does not start with the capture magic bytes
+    InvalidMagic,
+    /// The capture was written by an incompatible version
+    UnsupportedVersion(u16),
+    /// The capture was written with a different `DrawIdx` size
+    IndexSizeMismatch {
+        /// The size of `DrawIdx` in this build
+        expected: usize,
+        /// The size of the indices in the capture
+        found: usize,
+    },
+    /// The capture is malformed
+    Corrupt(&'static str),
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::Io(e) => write!(f, "failed to read capture: {}", e),
+            CaptureError::InvalidMagic => f.write_str("not a draw data capture"),
+            CaptureError::UnsupportedVersion(version) => {
+                write!(f, "unsupported capture version {}", version)
+            }
+            CaptureError::IndexSizeMismatch { expected, found } => write!(
+                f,
+                "capture uses {}-byte indices, expected {}-byte indices",
+                found, expected
+            ),
+            CaptureError::Corrupt(reason) => write!(f, "corrupt capture: {}", reason),
+        }
+    }
+}
+
+impl Error for CaptureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CaptureError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CaptureError {
+    fn from(e: io::Error) -> Self {
+        CaptureError::Io(e)
+    }
+}
+
+impl DrawDataCapture {
+    /// Creates an empty capture.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a copy of the given draw data as the next frame.
+    pub fn record(&mut self, draw_data: &DrawData) {
+        self.frames.push(CapturedFrame::new(draw_data));
+    }
+
+    /// Appends an already captured frame.
+    pub fn push(&mut self, frame: CapturedFrame) {
+        self.frames.push(frame);
+    }
+
+    /// Returns the recorded frames, in order.
+    pub fn frames(&self) -> &[CapturedFrame] {
+        &self.frames
+    }
+
+    /// Returns the number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frame was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns every texture id referenced by the recorded frames, sorted and without duplicates.
+    ///
+    /// A replaying application must provide these textures, or remap them with
+    /// [`CapturedFrame::replay_with_textures`].
+    pub fn texture_ids(&self) -> Vec<TextureId> {
+        let mut ids = BTreeSet::new();
+        for frame in &self.frames {
+            frame.collect_texture_ids(&mut ids);
+        }
+        ids.into_iter().map(TextureId::new).collect()
+    }
+
+    /// Writes the capture to a stream.
+    pub fn write_to<W: Write>(&self, mut w: W) -> io::Result<()> {
+        let textures = self.texture_ids();
+        w.write_all(&MAGIC)?;
+        w.write_all(&VERSION.to_le_bytes())?;
+        w.write_all(&[std::mem::size_of::<DrawIdx>() as u8])?;
+        write_len(&mut w, textures.len())?;
+        for id in &textures {
+            w.write_all(&(id.id() as u64).to_le_bytes())?;
+        }
+        write_len(&mut w, self.frames.len())?;
+        for frame in &self.frames {
+            frame.write_to(&mut w, &textures)?;
+        }
+        w.flush()
+    }
+
+    /// Reads a capture written by [`write_to`](Self::write_to).
+    ///
+    /// Every command is validated against the buffers of its draw list, so a replayed frame
+    /// never makes a renderer read out of bounds.
+    pub fn read_from<R: Read>(r: R) -> Result<Self, CaptureError> {
+        let mut r = Reader(r);
+        if r.array::<4>()? != MAGIC {
+            return Err(CaptureError::InvalidMagic);
+        }
+        let version = r.u16()?;
+        if version != VERSION {
+            return Err(CaptureError::UnsupportedVersion(version));
+        }
+        let index_size = r.u8()? as usize;
+        if index_size != std::mem::size_of::<DrawIdx>() {
+            return Err(CaptureError::IndexSizeMismatch {
+                expected: std::mem::size_of::<DrawIdx>(),
+                found: index_size,
+            });
+        }
+        let texture_count = r.len()?;
+        let mut textures = Vec::new();
+        for _ in 0..texture_count {
+            let id = usize::try_from(r.u64()?)
+                .map_err(|_| CaptureError::Corrupt("texture id does not fit in usize"))?;
+            textures.push(TextureId::new(id));
+        }
+        let frame_count = r.len()?;
+        let mut frames = Vec::new();
+        for _ in 0..frame_count {
+            frames.push(CapturedFrame::read_from(&mut r, &textures)?);
+        }
+        Ok(DrawDataCapture { frames })
+    }
+
+    /// Encodes the capture into a byte vector.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_to(&mut bytes)
+            .expect("writing to a Vec cannot fail");
+        bytes
+    }
+
+    /// Decodes a capture from a byte slice.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CaptureError> {
+        Self::read_from(bytes)
+    }
+
+    /// Writes the capture to a file, replacing it if it exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+
+    /// Reads a capture from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CaptureError> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+}
+
+impl CapturedFrame {
+    /// Copies the buffers of the given draw data.
+    pub fn new(draw_data: &DrawData) -> Self {
+        CapturedFrame {
+            display_pos: draw_data.display_pos,
+            display_size: draw_data.display_size,
+            framebuffer_scale: draw_data.framebuffer_scale,
+            lists: draw_data.draw_lists().map(CapturedDrawList::new).collect(),
+        }
+    }
+
+    /// Returns every texture id referenced by this frame, sorted and without duplicates.
+    pub fn texture_ids(&self) -> Vec<TextureId> {
+        let mut ids = BTreeSet::new();
+        self.collect_texture_ids(&mut ids);
+        ids.into_iter().map(TextureId::new).collect()
+    }
+
+    /// Rebuilds a [`DrawData`] from this frame.
+    ///
+    /// Fails with [`CaptureError::Corrupt`] if a command draws indices or vertices outside of
+    /// the buffers of its list, which a renderer would read out of bounds.
+    pub fn replay(&self) -> Result<ReplayFrame, CaptureError> {
+        self.replay_with_textures(|id| id)
+    }
+
+    /// Rebuilds a [`DrawData`] from this frame, replacing every texture id with the result of
+    /// `map`.
+    ///
+    /// Texture ids are only meaningful to the renderer that created them, so replaying a capture
+    /// with another renderer usually requires mapping them to textures it uploaded itself.
+    ///
+    /// Fails like [`replay`](Self::replay).
+    pub fn replay_with_textures<F>(&self, mut map: F) -> Result<ReplayFrame, CaptureError>
+    where
+        F: FnMut(TextureId) -> TextureId,
+    {
+        let mut lists = Vec::with_capacity(self.lists.len());
+        let mut total_vtx_count = 0;
+        let mut total_idx_count = 0;
+        for list in &self.lists {
+            for cmd in &list.commands {
+                if let CapturedDrawCmd::Elements { count, cmd_params } = *cmd {
+                    list.validate_elements(count, cmd_params.vtx_offset, cmd_params.idx_offset)?;
+                }
+            }
+            let vtx_buffer = list.vtx_buffer.clone();
+            let idx_buffer = list.idx_buffer.clone();
+            let cmd_buffer: Vec<sys::ImDrawCmd> = list
+                .commands
+                .iter()
+                .map(|cmd| match *cmd {
+                    CapturedDrawCmd::Elements { count, cmd_params } => sys::ImDrawCmd {
+                        ClipRect: cmd_params.clip_rect.into(),
+                        TextureId: map(cmd_params.texture_id).id() as sys::ImTextureID,
+                        VtxOffset: cmd_params.vtx_offset as u32,
+                        IdxOffset: cmd_params.idx_offset as u32,
+                        ElemCount: count as u32,
+                        ..Default::default()
+                    },
+                    CapturedDrawCmd::ResetRenderState => sys::ImDrawCmd {
+                        UserCallback: Some(unsafe {
+                            std::mem::transmute::<
+                                usize,
+                                unsafe extern "C" fn(*const sys::ImDrawList, *const sys::ImDrawCmd),
+                            >(RESET_RENDER_STATE_CALLBACK)
+                        }),
+                        ..Default::default()
+                    },
+                })
+                .collect();
+            total_vtx_count += vtx_buffer.len();
+            total_idx_count += idx_buffer.len();
+            lists.push(ReplayList::new(vtx_buffer, idx_buffer, cmd_buffer));
+        }
+        let mut list_ptrs: Vec<*mut sys::ImDrawList> = lists
+            .iter_mut()
+            .map(|list| &mut *list.raw as *mut _)
+            .collect();
+        let raw = Box::new(sys::ImDrawData {
+            Valid: true,
+            CmdListsCount: list_ptrs.len() as i32,
+            TotalIdxCount: total_idx_count as i32,
+            TotalVtxCount: total_vtx_count as i32,
+            CmdLists: sys::ImVector_ImDrawListPtr {
+                Size: list_ptrs.len() as i32,
+                Capacity: list_ptrs.len() as i32,
+                Data: list_ptrs.as_mut_ptr(),
+            },
+            DisplayPos: self.display_pos.into(),
+            DisplaySize: self.display_size.into(),
+            FramebufferScale: self.framebuffer_scale.into(),
+            OwnerViewport: std::ptr::null_mut(),
+        });
+        Ok(ReplayFrame {
+            raw,
+            _list_ptrs: list_ptrs,
+            _lists: lists,
+        })
+    }
+
+    fn collect_texture_ids(&self, ids: &mut BTreeSet<usize>) {
+        for list in &self.lists {
+            for cmd in &list.commands {
+                if let CapturedDrawCmd::Elements { cmd_params, .. } = cmd {
+                    ids.insert(cmd_params.texture_id.id());
+                }
+            }
+        }
+    }
+
+    fn write_to<W: Write>(&self, w: &mut W, textures: &[TextureId]) -> io::Result<()> {
+        for v in [self.display_pos, self.display_size, self.framebuffer_scale] {
+            write_f32s(w, &v)?;
+        }
+        write_len(w, self.lists.len())?;
+        for list in &self.lists {
+            list.write_to(w, textures)?;
+        }
+        Ok(())
+    }
+
+    fn read_from<R: Read>(r: &mut Reader<R>, textures: &[TextureId]) -> Result<Self, CaptureError> {
+        let display_pos = r.f32s()?;
+        let display_size = r.f32s()?;
+        let framebuffer_scale = r.f32s()?;
+        let list_count = r.len()?;
+        let mut lists = Vec::new();
+        for _ in 0..list_count {
+            lists.push(CapturedDrawList::read_from(r, textures)?);
+        }
+        Ok(CapturedFrame {
+            display_pos,
+            display_size,
+            framebuffer_scale,
+            lists,
+        })
+    }
+}
+
+impl CapturedDrawList {
+    /// Copies the buffers of the given draw list. User callbacks are skipped.
+    pub fn new(list: &DrawList) -> Self {
+        CapturedDrawList {
+            vtx_buffer: list.vtx_buffer().to_vec(),
+            idx_buffer: list.idx_buffer().to_vec(),
+            commands: list
+                .commands()
+                .filter_map(|cmd| match cmd {
+                    DrawCmd::Elements { count, cmd_params } => {
+                        Some(CapturedDrawCmd::Elements { count, cmd_params })
+                    }
+                    DrawCmd::ResetRenderState => Some(CapturedDrawCmd::ResetRenderState),
+                    DrawCmd::RawCallback { .. } => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the buffers, referring to textures by their position in the sorted `textures`.
+    pub(crate) fn write_to<W: Write>(&self, w: &mut W, textures: &[TextureId]) -> io::Result<()> {
+        write_len(w, self.vtx_buffer.len())?;
+        for vtx in &self.vtx_buffer {
+            write_f32s(w, &vtx.pos)?;
+            write_f32s(w, &vtx.uv)?;
+            w.write_all(&vtx.col)?;
+        }
+        write_len(w, self.idx_buffer.len())?;
+        for idx in &self.idx_buffer {
+            w.write_all(&idx.to_le_bytes())?;
+        }
+        write_len(w, self.commands.len())?;
+        for cmd in &self.commands {
+            match cmd {
+                CapturedDrawCmd::Elements { count, cmd_params } => {
+                    let texture = textures
+                        .binary_search_by_key(&cmd_params.texture_id.id(), |id| id.id())
+                        .expect("texture table contains every referenced texture");
+                    w.write_all(&[CMD_ELEMENTS])?;
+                    write_len(w, *count)?;
+                    write_f32s(w, &cmd_params.clip_rect)?;
+                    write_len(w, texture)?;
+                    write_len(w, cmd_params.vtx_offset)?;
+                    write_len(w, cmd_params.idx_offset)?;
+                }
+                CapturedDrawCmd::ResetRenderState => w.write_all(&[CMD_RESET_RENDER_STATE])?,
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn read_from<R: Read>(
+        r: &mut Reader<R>,
+        textures: &[TextureId],
+    ) -> Result<Self, CaptureError> {
+        let mut list = CapturedDrawList::default();
+        for _ in 0..r.len()? {
+            list.vtx_buffer.push(DrawVert {
+                pos: r.f32s()?,
+                uv: r.f32s()?,
+                col: r.array()?,
+            });
+        }
+        for _ in 0..r.len()? {
+            list.idx_buffer.push(DrawIdx::from_le_bytes(r.array()?));
+        }
+        for _ in 0..r.len()? {
+            let cmd = match r.u8()? {
+                CMD_ELEMENTS => {
+                    let count = r.len()?;
+                    let clip_rect = r.f32s()?;
+                    let texture_id = *textures
+                        .get(r.len()?)
+                        .ok_or(CaptureError::Corrupt("texture index out of range"))?;
+                    let vtx_offset = r.len()?;
+                    let idx_offset = r.len()?;
+                    list.validate_elements(count, vtx_offset, idx_offset)?;
+                    CapturedDrawCmd::Elements {
+                        count,
+                        cmd_params: DrawCmdParams {
+                            clip_rect,
+                            texture_id,
+                            vtx_offset,
+                            idx_offset,
+                        },
+                    }
+                }
+                CMD_RESET_RENDER_STATE => CapturedDrawCmd::ResetRenderState,
+                _ => return Err(CaptureError::Corrupt("unknown command")),
+            };
+            list.commands.push(cmd);
+        }
+        Ok(list)
+    }
+
+    fn validate_elements(
+        &self,
+        count: usize,
+        vtx_offset: usize,
+        idx_offset: usize,
+    ) -> Result<(), CaptureError> {
+        let indices = idx_offset
+            .checked_add(count)
+            .and_then(|end| self.idx_buffer.get(idx_offset..end))
+            .ok_or(CaptureError::Corrupt("command indices out of range"))?;
+        if indices.iter().any(|&idx| {
+            vtx_offset
+                .checked_add(idx as usize)
+                .is_none_or(|vtx| vtx >= self.vtx_buffer.len())
+        }) {
+            return Err(CaptureError::Corrupt("command vertices out of range"));
+        }
+        Ok(())
+    }
+}
+
+/// A [`DrawData`] rebuilt from a [`CapturedFrame`].
+///
+/// All buffers are owned by this struct, so it does not depend on a Dear ImGui context.
+pub struct ReplayFrame {
+    raw: Box<sys::ImDrawData>,
+    _list_ptrs: Vec<*mut sys::ImDrawList>,
+    _lists: Vec<ReplayList>,
+}
+
+impl ReplayFrame {
+    /// Returns the rebuilt draw data.
+    pub fn draw_data(&self) -> &DrawData {
+        unsafe { DrawData::from_raw(&self.raw) }
+    }
+}
+
+impl fmt::Debug for ReplayFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplayFrame")
+            .field("draw_lists_count", &self._lists.len())
+            .finish()
+    }
+}
+
+struct ReplayList {
+    raw: Box<sys::ImDrawList>,
+    _vtx_buffer: Vec<DrawVert>,
+    _idx_buffer: Vec<DrawIdx>,
+    _cmd_buffer: Vec<sys::ImDrawCmd>,
+}
+
+impl ReplayList {
+    fn new(
+        mut vtx_buffer: Vec<DrawVert>,
+        mut idx_buffer: Vec<DrawIdx>,
+        mut cmd_buffer: Vec<sys::ImDrawCmd>,
+    ) -> Self {
+        let mut raw = Box::<sys::ImDrawList>::default();
+        raw.VtxBuffer = sys::ImVector_ImDrawVert {
+            Size: vtx_buffer.len() as i32,
+            Capacity: vtx_buffer.len() as i32,
+            Data: vtx_buffer.as_mut_ptr() as *mut sys::ImDrawVert,
+        };
+        raw.IdxBuffer = sys::ImVector_ImDrawIdx {
+            Size: idx_buffer.len() as i32,
+            Capacity: idx_buffer.len() as i32,
+            Data: idx_buffer.as_mut_ptr(),
+        };
+        raw.CmdBuffer = sys::ImVector_ImDrawCmd {
+            Size: cmd_buffer.len() as i32,
+            Capacity: cmd_buffer.len() as i32,
+            Data: cmd_buffer.as_mut_ptr(),
+        };
+        ReplayList {
+            raw,
+            _vtx_buffer: vtx_buffer,
+            _idx_buffer: idx_buffer,
+            _cmd_buffer: cmd_buffer,
+        }
+    }
+}
+
+pub(crate) fn write_len<W: Write>(w: &mut W, len: usize) -> io::Result<()> {
+    let len = u32::try_from(len)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "length exceeds u32"))?;
+    w.write_all(&len.to_le_bytes())
+}
+
+pub(crate) fn write_f32s<W: Write>(w: &mut W, values: &[f32]) -> io::Result<()> {
+    for v in values {
+        w.write_all(&v.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+pub(crate) struct Reader<R>(pub(crate) R);
+
+impl<R: Read> Reader<R> {
+    pub(crate) fn array<const N: usize>(&mut self) -> Result<[u8; N], CaptureError> {
+        let mut buf = [0; N];
+        self.0.read_exact(&mut buf).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => CaptureError::Corrupt("unexpected end of data"),
+            _ => CaptureError::Io(e),
+        })?;
+        Ok(buf)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, CaptureError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, CaptureError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, CaptureError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn len(&mut self) -> Result<usize, CaptureError> {
+        Ok(u32::from_le_bytes(self.array()?) as usize)
+    }
+
+    pub(crate) fn f32s<const N: usize>(&mut self) -> Result<[f32; N], CaptureError> {
+        let mut values = [0.0; N];
+        for v in &mut values {
+            *v = f32::from_le_bytes(self.array()?);
+        }
+        Ok(values)
+    }
+}
+
+#[test]
+fn test_capture_round_trip() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut capture = DrawDataCapture::new();
+    for i in 0..2 {
+        let ui = ctx.new_frame();
+        ui.window("Capture")
+            .build(|| ui.text(format!("Frame {}", i)));
+        capture.record(ctx.render());
+    }
+    assert_eq!(capture.len(), 2);
+    assert_eq!(capture.texture_ids(), vec![ctx.fonts().tex_id]);
+
+    let bytes = capture.to_bytes();
+    assert_eq!(&bytes[..4], b"IMDC");
+    assert_eq!(DrawDataCapture::from_bytes(&bytes).unwrap(), capture);
+}
+
+#[test]
+fn test_capture_replay() {
+    use crate::render::snapshot::DrawDataSnapshot;
+    use crate::render::software::SoftwareRenderer;
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let renderer = SoftwareRenderer::new(&mut ctx);
+    // New windows are hidden during their first frame
+    for frame in 0..2 {
+        let ui = ctx.new_frame();
+        ui.window("Replay")
+            .position([10.0, 10.0], crate::Condition::Always)
+            .build(|| ui.text("Hi"));
+        if frame == 0 {
+            ctx.render();
+        }
+    }
+    let draw_data = ctx.render();
+    assert!(draw_data.draw_lists_count() > 0);
+    let expected_snapshot = DrawDataSnapshot::with_hashes(draw_data);
+    let expected = renderer.render(draw_data).unwrap();
+
+    let mut capture = DrawDataCapture::new();
+    capture.record(draw_data);
+    let capture = DrawDataCapture::from_bytes(&capture.to_bytes()).unwrap();
+    let frame = &capture.frames()[0];
+    let replay = frame.replay().unwrap();
+    assert_eq!(
+        DrawDataSnapshot::with_hashes(replay.draw_data()),
+        expected_snapshot
+    );
+    assert!(renderer.render(replay.draw_data()).unwrap() == expected);
+
+    let font = renderer.font_texture();
+    let remapped = frame
+        .replay_with_textures(|id| {
+            assert_eq!(id, font);
+            TextureId::new(42)
+        })
+        .unwrap();
+    assert_eq!(
+        CapturedFrame::new(remapped.draw_data()).texture_ids(),
+        vec![TextureId::new(42)]
+    );
+}
+
+#[test]
+fn test_capture_read_errors() {
+    let read = |bytes: &[u8]| DrawDataCapture::from_bytes(bytes).unwrap_err().to_string();
+    assert_eq!(read(b"PNG!"), "not a draw data capture");
+    assert_eq!(read(b"IMDC\x02\x00\x02"), "unsupported capture version 2");
+    assert_eq!(
+        read(b"IMDC\x01\x00\x09"),
+        format!(
+            "capture uses 9-byte indices, expected {}-byte indices",
+            std::mem::size_of::<DrawIdx>()
+        )
+    );
+
+    let frame = CapturedFrame {
+        display_pos: [0.0, 0.0],
+        display_size: [10.0, 10.0],
+        framebuffer_scale: [1.0, 1.0],
+        lists: vec![CapturedDrawList {
+            vtx_buffer: vec![
+                DrawVert {
+                    pos: [0.0, 0.0],
+                    uv: [0.0, 0.0],
+                    col: [255; 4],
+                };
+                3
+            ],
+            idx_buffer: vec![0, 1, 2],
+            commands: vec![CapturedDrawCmd::Elements {
+                count: 3,
+                cmd_params: DrawCmdParams {
+                    clip_rect: [0.0, 0.0, 10.0, 10.0],
+                    texture_id: TextureId::new(7),
+                    vtx_offset: 1,
+                    idx_offset: 0,
+                },
+            }],
+        }],
+    };
+    let mut capture = DrawDataCapture::new();
+    capture.push(frame);
+    let bytes = capture.to_bytes();
+    assert_eq!(
+        read(&bytes),
+        "corrupt capture: command vertices out of range"
+    );
+    assert_eq!(
+        read(&bytes[..bytes.len() - 1]),
+        "corrupt capture: unexpected end of data"
+    );
+
+    // Frames built in code are validated when replayed
+    let mut frame = capture.frames()[0].clone();
+    assert_eq!(
+        frame.replay().unwrap_err().to_string(),
+        "corrupt capture: command vertices out of range"
+    );
+    frame.lists[0].commands = vec![CapturedDrawCmd::Elements {
+        count: 3,
+        cmd_params: DrawCmdParams {
+            clip_rect: [0.0, 0.0, 10.0, 10.0],
+            texture_id: TextureId::new(7),
+            vtx_offset: usize::MAX,
+            idx_offset: 1,
+        },
+    }];
+    assert_eq!(
+        frame.replay().unwrap_err().to_string(),
+        "corrupt capture: command indices out of range"
+    );
+    frame.lists[0].commands[0] = CapturedDrawCmd::Elements {
+        count: 3,
+        cmd_params: DrawCmdParams {
+            clip_rect: [0.0, 0.0, 10.0, 10.0],
+            texture_id: TextureId::new(7),
+            vtx_offset: usize::MAX,
+            idx_offset: 0,
+        },
+    };
+    assert_eq!(
+        frame.replay().unwrap_err().to_string(),
+        "corrupt capture: command vertices out of range"
+    );
+}
+
+#[test]
+fn test_capture_reset_render_state() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    // New windows are hidden during their first frame
+    for _ in 0..2 {
+        let ui = ctx.new_frame();
+        ui.window("Reset").build(|| unsafe {
+            // ImDrawCallback_ResetRenderState, as added by C++ code
+            let callback = std::mem::transmute::<
+                usize,
+                unsafe extern "C" fn(*const sys::ImDrawList, *const sys::ImDrawCmd),
+            >(-8isize as usize);
+            let draw_list = sys::igGetWindowDrawList();
+            sys::ImDrawList_AddCallback(draw_list, Some(callback), std::ptr::null_mut());
+            ui.text("Reset");
+        });
+        ctx.render();
+    }
+    let frame = CapturedFrame::new(ctx.render());
+    assert!(frame
+        .lists
+        .iter()
+        .any(|list| list.commands.contains(&CapturedDrawCmd::ResetRenderState)));
+
+    let replay = frame.replay().unwrap();
+    let callbacks: Vec<usize> = replay
+        ._lists
+        .iter()
+        .flat_map(|list| &list._cmd_buffer)
+        .filter_map(|cmd| cmd.UserCallback.map(|callback| callback as usize))
+        .collect();
+    assert_eq!(callbacks, [-8isize as usize]);
+    assert_eq!(CapturedFrame::new(replay.draw_data()), frame);
+}
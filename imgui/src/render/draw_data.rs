@@ -234,7 +234,7 @@ impl Iterator for DrawCmdIterator<'_> {
                 idx_offset: cmd.IdxOffset as usize,
             };
             match cmd.UserCallback {
-                Some(raw_callback) if raw_callback as usize == -1isize as usize => {
+                Some(raw_callback) if raw_callback as usize == RESET_RENDER_STATE_CALLBACK => {
                     DrawCmd::ResetRenderState
                 }
                 Some(raw_callback) => DrawCmd::RawCallback {
@@ -250,6 +250,10 @@ impl Iterator for DrawCmdIterator<'_> {
     }
 }
 
+/// The value of Dear ImGui's `ImDrawCallback_ResetRenderState`, a callback that asks the
+/// renderer to reset its render state instead of being called.
+pub(crate) const RESET_RENDER_STATE_CALLBACK: usize = -8isize as usize;
+
 /// A vertex index
 pub type DrawIdx = sys::ImDrawIdx;
 
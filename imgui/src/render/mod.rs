@@ -1,2 +1,5 @@
+pub mod capture;
 pub mod draw_data;
 pub mod renderer;
+pub mod snapshot;
+pub mod software;
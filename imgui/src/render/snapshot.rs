@@ -0,0 +1,560 @@
+//! A canonical, human-readable representation of [`DrawData`].
+//!
+//! [`DrawDataSnapshot`] captures the structure of a frame: every draw list with its vertex and
+//! index counts, and every command with its clip rect, texture id and buffer offsets. Vertex and
+//! index data can optionally be summarized by a hash. Snapshots are written with
+//! [`Display`](fmt::Display), parsed back with [`FromStr`], and compared with
+//! [`DrawDataSnapshot::diff`], which makes them suitable for regression tests that should catch
+//! layout changes without comparing pixels.
+//!
+//! The text format has one line per draw data, draw list and command:
+//!
+//! ```text
+//! draw_data display_pos=0,0 display_size=1024,768 framebuffer_scale=1,1
+//! list vtx=212 idx=366 vtx_hash=6b3d0fc4a7e2b1d3 idx_hash=0c1e4d44d2a9b5f1
+//!   elements count=366 clip=0,0,1024,768 texture=0 vtx_offset=0 idx_offset=0
+//!   reset_render_state
+//!   callback
+//! ```
+//!
+//! Floating point values use their shortest exact representation, so a snapshot survives a round
+//! trip through text unchanged.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::render::draw_data::{DrawCmd, DrawData, DrawList, DrawVert};
+use crate::render::renderer::TextureId;
+
+/// An owned description of the structure of a [`DrawData`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawDataSnapshot {
+    /// Upper-left position of the viewport
+    pub display_pos: [f32; 2],
+    /// Size of the viewport
+    pub display_size: [f32; 2],
+    /// Amount of pixels for each unit of display size
+    pub framebuffer_scale: [f32; 2],
+    /// The draw lists, in rendering order
+    pub lists: Vec<DrawListSnapshot>,
+}
+
+/// An owned description of a [`DrawList`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawListSnapshot {
+    /// Number of vertices in the vertex buffer
+    pub vtx_count: usize,
+    /// Number of indices in the index buffer
+    pub idx_count: usize,
+    /// Hash of the vertex buffer, if requested
+    pub vtx_hash: Option<u64>,
+    /// Hash of the index buffer, if requested
+    pub idx_hash: Option<u64>,
+    /// The draw commands, in order
+    pub commands: Vec<DrawCmdSnapshot>,
+}
+
+/// An owned description of a [`DrawCmd`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DrawCmdSnapshot {
+    /// [`DrawCmd::Elements`]
+    Elements {
+        /// The number of indices used for this draw command
+        count: usize,
+        /// left, up, right, down
+        clip_rect: [f32; 4],
+        texture_id: TextureId,
+        vtx_offset: usize,
+        idx_offset: usize,
+    },
+    /// [`DrawCmd::ResetRenderState`]
+    ResetRenderState,
+    /// [`DrawCmd::RawCallback`]. The callback itself is not recorded.
+    Callback,
+}
+
+impl DrawDataSnapshot {
+    /// Captures the structure of the draw data, without hashing vertex and index data.
+    pub fn new(draw_data: &DrawData) -> Self {
+        Self::capture(draw_data, false)
+    }
+
+    /// Captures the structure of the draw data, including hashes of the vertex and index buffers
+    /// of each draw list.
+    pub fn with_hashes(draw_data: &DrawData) -> Self {
+        Self::capture(draw_data, true)
+    }
+
+    fn capture(draw_data: &DrawData, hashes: bool) -> Self {
+        DrawDataSnapshot {
+            display_pos: draw_data.display_pos,
+            display_size: draw_data.display_size,
+            framebuffer_scale: draw_data.framebuffer_scale,
+            lists: draw_data
+                .draw_lists()
+                .map(|list| DrawListSnapshot::capture(list, hashes))
+                .collect(),
+        }
+    }
+
+    /// Compares two snapshots, returning every difference between them.
+    ///
+    /// Draw lists and commands are compared by position. Hashes are only compared if both
+    /// snapshots have them.
+    pub fn diff(&self, other: &DrawDataSnapshot) -> Vec<Difference> {
+        let mut diffs = Vec::new();
+        let mut push = |path: String, before: String, after: String| {
+            if before != after {
+                diffs.push(Difference {
+                    path,
+                    before,
+                    after,
+                })
+            }
+        };
+        push(
+            "display_pos".into(),
+            Pair(self.display_pos).to_string(),
+            Pair(other.display_pos).to_string(),
+        );
+        push(
+            "display_size".into(),
+            Pair(self.display_size).to_string(),
+            Pair(other.display_size).to_string(),
+        );
+        push(
+            "framebuffer_scale".into(),
+            Pair(self.framebuffer_scale).to_string(),
+            Pair(other.framebuffer_scale).to_string(),
+        );
+        for i in 0..self.lists.len().max(other.lists.len()) {
+            let path = format!("lists[{}]", i);
+            let (before, after) = match (self.lists.get(i), other.lists.get(i)) {
+                (Some(before), Some(after)) => (before, after),
+                (before, after) => {
+                    push(path, describe(before), describe(after));
+                    continue;
+                }
+            };
+            push(
+                format!("{}.vtx", path),
+                before.vtx_count.to_string(),
+                after.vtx_count.to_string(),
+            );
+            push(
+                format!("{}.idx", path),
+                before.idx_count.to_string(),
+                after.idx_count.to_string(),
+            );
+            if let (Some(b), Some(a)) = (before.vtx_hash, after.vtx_hash) {
+                push(
+                    format!("{}.vtx_hash", path),
+                    Hash(b).to_string(),
+                    Hash(a).to_string(),
+                );
+            }
+            if let (Some(b), Some(a)) = (before.idx_hash, after.idx_hash) {
+                push(
+                    format!("{}.idx_hash", path),
+                    Hash(b).to_string(),
+                    Hash(a).to_string(),
+                );
+            }
+            for j in 0..before.commands.len().max(after.commands.len()) {
+                push(
+                    format!("{}.commands[{}]", path, j),
+                    describe(before.commands.get(j)),
+                    describe(after.commands.get(j)),
+                );
+            }
+        }
+        diffs
+    }
+}
+
+impl DrawListSnapshot {
+    fn capture(list: &DrawList, hashes: bool) -> Self {
+        let vtx_buffer = list.vtx_buffer();
+        let idx_buffer = list.idx_buffer();
+        DrawListSnapshot {
+            vtx_count: vtx_buffer.len(),
+            idx_count: idx_buffer.len(),
+            vtx_hash: hashes.then(|| hash_vertices(vtx_buffer)),
+            idx_hash: hashes.then(|| {
+                let mut hash = Fnv1a::new();
+                for &idx in idx_buffer {
+                    hash.write(&(idx as u32).to_le_bytes());
+                }
+                hash.finish()
+            }),
+            commands: list
+                .commands()
+                .map(|cmd| match cmd {
+                    DrawCmd::Elements { count, cmd_params } => DrawCmdSnapshot::Elements {
+                        count,
+                        clip_rect: cmd_params.clip_rect,
+                        texture_id: cmd_params.texture_id,
+                        vtx_offset: cmd_params.vtx_offset,
+                        idx_offset: cmd_params.idx_offset,
+                    },
+                    DrawCmd::ResetRenderState => DrawCmdSnapshot::ResetRenderState,
+                    DrawCmd::RawCallback { .. } => DrawCmdSnapshot::Callback,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Hashes vertex data independently of platform endianness.
+fn hash_vertices(vertices: &[DrawVert]) -> u64 {
+    let mut hash = Fnv1a::new();
+    for vtx in vertices {
+        for value in vtx.pos.iter().chain(&vtx.uv) {
+            hash.write(&value.to_bits().to_le_bytes());
+        }
+        hash.write(&vtx.col);
+    }
+    hash.finish()
+}
+
+/// 64-bit FNV-1a, which (unlike `DefaultHasher`) is stable across Rust versions.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// A single difference found by [`DrawDataSnapshot::diff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Difference {
+    /// Location of the difference, e.g. `lists[1].commands[3]`
+    pub path: String,
+    /// Textual value in the first snapshot (`-` if missing)
+    pub before: String,
+    /// Textual value in the second snapshot (`-` if missing)
+    pub after: String,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.path, self.before, self.after)
+    }
+}
+
+fn describe<T: fmt::Display>(value: Option<&T>) -> String {
+    value.map_or_else(|| "-".to_owned(), |value| value.to_string())
+}
+
+struct Pair([f32; 2]);
+
+impl fmt::Display for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.0[0], self.0[1])
+    }
+}
+
+struct Hash(u64);
+
+impl fmt::Display for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl fmt::Display for DrawDataSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "draw_data display_pos={} display_size={} framebuffer_scale={}",
+            Pair(self.display_pos),
+            Pair(self.display_size),
+            Pair(self.framebuffer_scale)
+        )?;
+        for list in &self.lists {
+            writeln!(f, "{}", list)?;
+            for command in &list.commands {
+                writeln!(f, "  {}", command)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the `list` line, without the commands.
+impl fmt::Display for DrawListSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "list vtx={} idx={}", self.vtx_count, self.idx_count)?;
+        if let Some(hash) = self.vtx_hash {
+            write!(f, " vtx_hash={}", Hash(hash))?;
+        }
+        if let Some(hash) = self.idx_hash {
+            write!(f, " idx_hash={}", Hash(hash))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DrawCmdSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DrawCmdSnapshot::Elements {
+                count,
+                clip_rect: [x1, y1, x2, y2],
+                texture_id,
+                vtx_offset,
+                idx_offset,
+            } => write!(
+                f,
+                "elements count={} clip={},{},{},{} texture={} vtx_offset={} idx_offset={}",
+                count,
+                x1,
+                y1,
+                x2,
+                y2,
+                texture_id.id(),
+                vtx_offset,
+                idx_offset
+            ),
+            DrawCmdSnapshot::ResetRenderState => f.write_str("reset_render_state"),
+            DrawCmdSnapshot::Callback => f.write_str("callback"),
+        }
+    }
+}
+
+/// An error returned when parsing a [`DrawDataSnapshot`] fails
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseSnapshotError {
+    line: usize,
+    message: String,
+}
+
+impl ParseSnapshotError {
+    /// Returns the 1-based line number the error occurred on.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseSnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseSnapshotError {}
+
+/// The `key=value` fields of a line.
+struct Fields<'a> {
+    fields: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Fields<'a> {
+    fn parse(tokens: impl Iterator<Item = &'a str>) -> Result<Self, String> {
+        let fields = tokens
+            .map(|token| {
+                token
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected key=value, found {:?}", token))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Fields { fields })
+    }
+
+    fn get(&self, key: &str) -> Option<&'a str> {
+        self.fields.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    }
+
+    fn value<T: FromStr>(&self, key: &str) -> Result<T, String> {
+        let value = self
+            .get(key)
+            .ok_or_else(|| format!("missing field {:?}", key))?;
+        value
+            .parse()
+            .map_err(|_| format!("invalid value {:?} for field {:?}", value, key))
+    }
+
+    fn floats<const N: usize>(&self, key: &str) -> Result<[f32; N], String> {
+        let value = self
+            .get(key)
+            .ok_or_else(|| format!("missing field {:?}", key))?;
+        let invalid = || format!("invalid value {:?} for field {:?}", value, key);
+        let mut out = [0.0; N];
+        let mut parts = value.split(',');
+        for out in out.iter_mut() {
+            *out = parts
+                .next()
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(invalid)?;
+        }
+        match parts.next() {
+            Some(_) => Err(invalid()),
+            None => Ok(out),
+        }
+    }
+
+    fn hash(&self, key: &str) -> Result<Option<u64>, String> {
+        self.get(key)
+            .map(|value| {
+                u64::from_str_radix(value, 16)
+                    .map_err(|_| format!("invalid value {:?} for field {:?}", value, key))
+            })
+            .transpose()
+    }
+}
+
+impl FromStr for DrawDataSnapshot {
+    type Err = ParseSnapshotError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut snapshot: Option<DrawDataSnapshot> = None;
+        for (i, line) in s.lines().enumerate() {
+            let error = |message: String| ParseSnapshotError {
+                line: i + 1,
+                message,
+            };
+            let mut tokens = line.split_whitespace();
+            let kind = match tokens.next() {
+                Some(kind) => kind,
+                None => continue,
+            };
+            let fields = Fields::parse(tokens).map_err(error)?;
+            match (kind, snapshot.as_mut()) {
+                ("draw_data", None) => {
+                    snapshot = Some(DrawDataSnapshot {
+                        display_pos: fields.floats("display_pos").map_err(error)?,
+                        display_size: fields.floats("display_size").map_err(error)?,
+                        framebuffer_scale: fields.floats("framebuffer_scale").map_err(error)?,
+                        lists: Vec::new(),
+                    })
+                }
+                ("list", Some(snapshot)) => snapshot.lists.push(DrawListSnapshot {
+                    vtx_count: fields.value("vtx").map_err(error)?,
+                    idx_count: fields.value("idx").map_err(error)?,
+                    vtx_hash: fields.hash("vtx_hash").map_err(error)?,
+                    idx_hash: fields.hash("idx_hash").map_err(error)?,
+                    commands: Vec::new(),
+                }),
+                ("elements" | "reset_render_state" | "callback", Some(snapshot)) => {
+                    let command = match kind {
+                        "elements" => DrawCmdSnapshot::Elements {
+                            count: fields.value("count").map_err(error)?,
+                            clip_rect: fields.floats("clip").map_err(error)?,
+                            texture_id: TextureId::new(fields.value("texture").map_err(error)?),
+                            vtx_offset: fields.value("vtx_offset").map_err(error)?,
+                            idx_offset: fields.value("idx_offset").map_err(error)?,
+                        },
+                        "reset_render_state" => DrawCmdSnapshot::ResetRenderState,
+                        _ => DrawCmdSnapshot::Callback,
+                    };
+                    snapshot
+                        .lists
+                        .last_mut()
+                        .ok_or_else(|| error("command outside of a list".into()))?
+                        .commands
+                        .push(command);
+                }
+                ("draw_data", Some(_)) => return Err(error("duplicate draw_data line".into())),
+                (_, None) => return Err(error("expected draw_data line".into())),
+                (kind, _) => return Err(error(format!("unknown line kind {:?}", kind))),
+            }
+        }
+        snapshot.ok_or(ParseSnapshotError {
+            line: 1,
+            message: "empty snapshot".into(),
+        })
+    }
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.new_frame();
+    ui.window("Snapshot").build(|| ui.text("Hello"));
+    ui.get_foreground_draw_list()
+        .add_line([0.5, 0.25], [10.1, 3.3], [1.0, 0.0, 0.0, 1.0])
+        .build();
+    let draw_data = ctx.render();
+
+    for snapshot in [
+        DrawDataSnapshot::new(draw_data),
+        DrawDataSnapshot::with_hashes(draw_data),
+    ] {
+        assert_eq!(snapshot.lists.len(), draw_data.draw_lists_count());
+        let text = snapshot.to_string();
+        assert_eq!(text.parse::<DrawDataSnapshot>(), Ok(snapshot));
+    }
+    assert!(DrawDataSnapshot::with_hashes(draw_data)
+        .to_string()
+        .contains(" vtx_hash="));
+}
+
+#[test]
+fn test_snapshot_parse_errors() {
+    let parse = |s: &str| s.parse::<DrawDataSnapshot>().map_err(|e| e.to_string());
+    assert_eq!(parse(""), Err("line 1: empty snapshot".into()));
+    assert_eq!(
+        parse("list vtx=1 idx=1"),
+        Err("line 1: expected draw_data line".into())
+    );
+    let header = "draw_data display_pos=0,0 display_size=10,10 framebuffer_scale=1,1\n";
+    assert_eq!(
+        parse(&format!("{}  callback", header)),
+        Err("line 2: command outside of a list".into())
+    );
+    assert_eq!(
+        parse(&format!("{}list vtx=x idx=1", header)),
+        Err("line 2: invalid value \"x\" for field \"vtx\"".into())
+    );
+    assert_eq!(
+        parse(&format!(
+            "{}list vtx=1 idx=1\n  elements count=3 clip=0,0,1",
+            header
+        )),
+        Err("line 3: invalid value \"0,0,1\" for field \"clip\"".into())
+    );
+}
+
+#[test]
+fn test_snapshot_diff() {
+    let before: DrawDataSnapshot =
+        "draw_data display_pos=0,0 display_size=10,10 framebuffer_scale=1,1
+list vtx=4 idx=6 vtx_hash=00000000000000ff
+  elements count=6 clip=0,0,10,10 texture=0 vtx_offset=0 idx_offset=0
+"
+        .parse()
+        .unwrap();
+    let after: DrawDataSnapshot =
+        "draw_data display_pos=0,0 display_size=10,10 framebuffer_scale=1,1
+list vtx=4 idx=6
+  elements count=6 clip=0,0,10,12 texture=0 vtx_offset=0 idx_offset=0
+  callback
+list vtx=0 idx=0
+"
+        .parse()
+        .unwrap();
+    let diff: Vec<String> = before.diff(&after).iter().map(|d| d.to_string()).collect();
+    assert_eq!(
+        diff,
+        vec![
+            "lists[0].commands[0]: elements count=6 clip=0,0,10,10 texture=0 vtx_offset=0 \
+             idx_offset=0 -> elements count=6 clip=0,0,10,12 texture=0 vtx_offset=0 idx_offset=0",
+            "lists[0].commands[1]: - -> callback",
+            "lists[1]: - -> list vtx=0 idx=0",
+        ]
+    );
+    assert!(before.diff(&before).is_empty());
+}
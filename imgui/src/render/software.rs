@@ -0,0 +1,494 @@
+//! A headless CPU rasterizer for [`DrawData`].
+//!
+//! This renderer draws a frame into an in-memory RGBA8 image without any GPU or windowing
+//! system. It is meant as a reference renderer for screenshot tests and for generating
+//! thumbnails on servers, not for interactive use: everything is done on a single thread with
+//! nearest-neighbour texture sampling.
+//!
+//! ```no_run
+//! # use imgui::*;
+//! # use imgui::render::software::SoftwareRenderer;
+//! let mut ctx = Context::create();
+//! ctx.io_mut().display_size = [320.0, 240.0];
+//! let renderer = SoftwareRenderer::new(&mut ctx);
+//!
+//! let ui = ctx.new_frame();
+//! ui.window("Hello").build(|| ui.text("Hello world!"));
+//! let image = renderer.render(ctx.render()).unwrap();
+//! assert_eq!((image.width(), image.height()), (320, 240));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::internal::RawWrapper;
+use crate::render::draw_data::{DrawCmd, DrawCmdParams, DrawData, DrawList, DrawVert};
+use crate::render::renderer::{TextureId, Textures};
+use crate::{Context, FontAtlas, FontAtlasTexture};
+
+/// An owned RGBA8 image, stored row by row with no padding.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RgbaImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Creates a fully transparent image of the given size.
+    pub fn new(width: u32, height: u32) -> Self {
+        RgbaImage {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Creates an image from raw RGBA8 pixels.
+    ///
+    /// Returns `None` if `pixels` does not hold exactly `width * height * 4` bytes.
+    pub fn from_raw(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        if pixels.len() == width as usize * height as usize * 4 {
+            Some(RgbaImage {
+                width,
+                height,
+                pixels,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Copies a texture obtained from [`FontAtlas::build_rgba32_texture`].
+    pub fn from_font_atlas_texture(texture: &FontAtlasTexture<'_>) -> Self {
+        RgbaImage::from_raw(texture.width, texture.height, texture.data.to_vec())
+            .expect("Font atlas texture is not in RGBA32 format")
+    }
+
+    /// Image width (in pixels)
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Image height (in pixels)
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Raw RGBA8 pixel data
+    #[inline]
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Mutable raw RGBA8 pixel data
+    #[inline]
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+
+    /// Consumes the image, returning the raw RGBA8 pixel data.
+    #[inline]
+    pub fn into_raw(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    /// Returns the pixel at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinates are out of bounds.
+    #[inline]
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let offset = self.offset(x, y);
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[offset..offset + 4]);
+        pixel
+    }
+
+    /// Replaces the pixel at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinates are out of bounds.
+    #[inline]
+    pub fn set_pixel(&mut self, x: u32, y: u32, pixel: [u8; 4]) {
+        let offset = self.offset(x, y);
+        self.pixels[offset..offset + 4].copy_from_slice(&pixel);
+    }
+
+    /// Sets every pixel of the image to `pixel`.
+    pub fn fill(&mut self, pixel: [u8; 4]) {
+        for chunk in self.pixels.chunks_exact_mut(4) {
+            chunk.copy_from_slice(&pixel);
+        }
+    }
+
+    #[inline]
+    fn offset(&self, x: u32, y: u32) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "Pixel ({}, {}) is out of bounds",
+            x,
+            y
+        );
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    /// Nearest-neighbour sample with clamp-to-edge addressing.
+    #[inline]
+    fn sample(&self, uv: [f32; 2]) -> [f32; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [1.0; 4];
+        }
+        let x = (uv[0] * self.width as f32).floor() as i64;
+        let y = (uv[1] * self.height as f32).floor() as i64;
+        let x = x.clamp(0, self.width as i64 - 1) as u32;
+        let y = y.clamp(0, self.height as i64 - 1) as u32;
+        let [r, g, b, a] = self.pixel(x, y);
+        [
+            r as f32 / 255.0,
+            g as f32 / 255.0,
+            b as f32 / 255.0,
+            a as f32 / 255.0,
+        ]
+    }
+}
+
+/// An error that occurred while rasterizing draw data
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SoftwareRendererError {
+    /// A draw command referenced a texture that is not present in the texture map
+    BadTexture(TextureId),
+}
+
+impl fmt::Display for SoftwareRendererError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SoftwareRendererError::BadTexture(id) => {
+                write!(f, "Draw command references unknown texture {}", id.id())
+            }
+        }
+    }
+}
+
+impl Error for SoftwareRendererError {}
+
+/// A CPU renderer which rasterizes [`DrawData`] into an [`RgbaImage`].
+///
+/// Textures are looked up in a [`Textures<RgbaImage>`] map. [`SoftwareRenderer::new`] uploads
+/// the font atlas into this map and sets the atlas texture id, so text renders out of the box.
+#[derive(Debug)]
+pub struct SoftwareRenderer {
+    textures: Textures<RgbaImage>,
+    font_texture: TextureId,
+    clear_color: [u8; 4],
+}
+
+impl SoftwareRenderer {
+    /// Creates a renderer, building the context's font atlas as an RGBA32 texture.
+    pub fn new(ctx: &mut Context) -> Self {
+        let mut textures = Textures::new();
+        let font_texture = Self::upload_font_texture(&mut textures, ctx.fonts());
+        SoftwareRenderer {
+            textures,
+            font_texture,
+            clear_color: [0, 0, 0, 0],
+        }
+    }
+
+    /// Rebuilds and re-uploads the font atlas texture, e.g. after fonts were added.
+    pub fn reload_font_texture(&mut self, fonts: &mut FontAtlas) {
+        self.textures.remove(self.font_texture);
+        self.font_texture = Self::upload_font_texture(&mut self.textures, fonts);
+    }
+
+    fn upload_font_texture(textures: &mut Textures<RgbaImage>, fonts: &mut FontAtlas) -> TextureId {
+        let image = RgbaImage::from_font_atlas_texture(&fonts.build_rgba32_texture());
+        let id = textures.insert(image);
+        fonts.tex_id = id;
+        id
+    }
+
+    /// Returns the texture id of the font atlas.
+    #[inline]
+    pub fn font_texture(&self) -> TextureId {
+        self.font_texture
+    }
+
+    /// Returns the texture map used when sampling.
+    #[inline]
+    pub fn textures(&self) -> &Textures<RgbaImage> {
+        &self.textures
+    }
+
+    /// Returns the texture map used when sampling, for registering user textures.
+    #[inline]
+    pub fn textures_mut(&mut self) -> &mut Textures<RgbaImage> {
+        &mut self.textures
+    }
+
+    /// Sets the colour that [`render`](Self::render) clears the image to.
+    ///
+    /// Defaults to fully transparent black.
+    #[inline]
+    pub fn set_clear_color(&mut self, color: [u8; 4]) {
+        self.clear_color = color;
+    }
+
+    /// Renders the draw data into a new image.
+    ///
+    /// The image size is `display_size * framebuffer_scale`, rounded to whole pixels.
+    pub fn render(&self, draw_data: &DrawData) -> Result<RgbaImage, SoftwareRendererError> {
+        let width = (draw_data.display_size[0] * draw_data.framebuffer_scale[0]).round();
+        let height = (draw_data.display_size[1] * draw_data.framebuffer_scale[1]).round();
+        let mut image = RgbaImage::new(width.max(0.0) as u32, height.max(0.0) as u32);
+        image.fill(self.clear_color);
+        self.render_into(draw_data, &mut image)?;
+        Ok(image)
+    }
+
+    /// Renders the draw data on top of an existing image.
+    pub fn render_into(
+        &self,
+        draw_data: &DrawData,
+        target: &mut RgbaImage,
+    ) -> Result<(), SoftwareRendererError> {
+        render_draw_data(draw_data, &self.textures, target)
+    }
+}
+
+/// Rasterizes draw data into `target`, sampling textures from `textures`.
+///
+/// Draw commands are clipped to their clip rects and to the bounds of `target`. Triangles are
+/// blended with straight alpha (`src * a + dst * (1 - a)`), like the reference backends.
+pub fn render_draw_data(
+    draw_data: &DrawData,
+    textures: &Textures<RgbaImage>,
+    target: &mut RgbaImage,
+) -> Result<(), SoftwareRendererError> {
+    let clip_off = draw_data.display_pos;
+    let clip_scale = draw_data.framebuffer_scale;
+    for draw_list in draw_data.draw_lists() {
+        let vtx_buffer = draw_list.vtx_buffer();
+        let idx_buffer = draw_list.idx_buffer();
+        for command in draw_list.commands() {
+            match command {
+                DrawCmd::Elements {
+                    count,
+                    cmd_params:
+                        DrawCmdParams {
+                            clip_rect,
+                            texture_id,
+                            vtx_offset,
+                            idx_offset,
+                        },
+                } => {
+                    let texture = textures
+                        .get(texture_id)
+                        .ok_or(SoftwareRendererError::BadTexture(texture_id))?;
+                    let clip_rect = [
+                        (clip_rect[0] - clip_off[0]) * clip_scale[0],
+                        (clip_rect[1] - clip_off[1]) * clip_scale[1],
+                        (clip_rect[2] - clip_off[0]) * clip_scale[0],
+                        (clip_rect[3] - clip_off[1]) * clip_scale[1],
+                    ];
+                    let to_target = |vtx: &DrawVert| Vertex {
+                        pos: [
+                            (vtx.pos[0] - clip_off[0]) * clip_scale[0],
+                            (vtx.pos[1] - clip_off[1]) * clip_scale[1],
+                        ],
+                        uv: vtx.uv,
+                        col: [
+                            vtx.col[0] as f32 / 255.0,
+                            vtx.col[1] as f32 / 255.0,
+                            vtx.col[2] as f32 / 255.0,
+                            vtx.col[3] as f32 / 255.0,
+                        ],
+                    };
+                    for triangle in idx_buffer[idx_offset..idx_offset + count].chunks_exact(3) {
+                        let vertex =
+                            |i: usize| to_target(&vtx_buffer[vtx_offset + triangle[i] as usize]);
+                        rasterize_triangle(
+                            target,
+                            texture,
+                            clip_rect,
+                            [vertex(0), vertex(1), vertex(2)],
+                        );
+                    }
+                }
+                DrawCmd::ResetRenderState => (),
+                DrawCmd::RawCallback { callback, raw_cmd } => unsafe {
+                    callback(DrawList::raw(draw_list), raw_cmd)
+                },
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Copy, Clone)]
+struct Vertex {
+    pos: [f32; 2],
+    uv: [f32; 2],
+    col: [f32; 4],
+}
+
+#[inline]
+fn edge(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+/// Whether pixel centres lying exactly on the edge `a -> b` belong to this triangle.
+///
+/// Adjacent triangles traverse a shared edge in opposite directions, so exactly one of them owns
+/// it and translucent shapes made of several triangles are not blended twice along the seams.
+#[inline]
+fn owns_edge(a: [f32; 2], b: [f32; 2]) -> bool {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    dy > 0.0 || (dy == 0.0 && dx < 0.0)
+}
+
+fn rasterize_triangle(
+    target: &mut RgbaImage,
+    texture: &RgbaImage,
+    clip_rect: [f32; 4],
+    vertices: [Vertex; 3],
+) {
+    let [v0, mut v1, mut v2] = vertices;
+    let mut area = edge(v0.pos, v1.pos, v2.pos);
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+    if area < 0.0 {
+        std::mem::swap(&mut v1, &mut v2);
+        area = -area;
+    }
+
+    let min_x = v0.pos[0].min(v1.pos[0]).min(v2.pos[0]).max(clip_rect[0]);
+    let min_y = v0.pos[1].min(v1.pos[1]).min(v2.pos[1]).max(clip_rect[1]);
+    let max_x = v0.pos[0].max(v1.pos[0]).max(v2.pos[0]).min(clip_rect[2]);
+    let max_y = v0.pos[1].max(v1.pos[1]).max(v2.pos[1]).min(clip_rect[3]);
+    // Pixel (x, y) is covered when its centre (x + 0.5, y + 0.5) lies inside the triangle.
+    let x_start = (min_x - 0.5).ceil().max(0.0) as u32;
+    let y_start = (min_y - 0.5).ceil().max(0.0) as u32;
+    let x_end = ((max_x - 0.5).ceil().max(0.0) as u32).min(target.width);
+    let y_end = ((max_y - 0.5).ceil().max(0.0) as u32).min(target.height);
+
+    let owns = [
+        owns_edge(v1.pos, v2.pos),
+        owns_edge(v2.pos, v0.pos),
+        owns_edge(v0.pos, v1.pos),
+    ];
+    for y in y_start..y_end {
+        for x in x_start..x_end {
+            let p = [x as f32 + 0.5, y as f32 + 0.5];
+            let w = [
+                edge(v1.pos, v2.pos, p),
+                edge(v2.pos, v0.pos, p),
+                edge(v0.pos, v1.pos, p),
+            ];
+            if !(0..3).all(|i| w[i] > 0.0 || (w[i] == 0.0 && owns[i])) {
+                continue;
+            }
+            let (b0, b1, b2) = (w[0] / area, w[1] / area, w[2] / area);
+            let uv = [
+                v0.uv[0] * b0 + v1.uv[0] * b1 + v2.uv[0] * b2,
+                v0.uv[1] * b0 + v1.uv[1] * b1 + v2.uv[1] * b2,
+            ];
+            let texel = texture.sample(uv);
+            let mut src = [0.0; 4];
+            for (c, out) in src.iter_mut().enumerate() {
+                let col = v0.col[c] * b0 + v1.col[c] * b1 + v2.col[c] * b2;
+                *out = (col * texel[c]).clamp(0.0, 1.0);
+            }
+            blend(target, x, y, src);
+        }
+    }
+}
+
+#[inline]
+fn blend(target: &mut RgbaImage, x: u32, y: u32, src: [f32; 4]) {
+    let dst = target.pixel(x, y);
+    let alpha = src[3];
+    let mut out = [0; 4];
+    for c in 0..3 {
+        let value = src[c] * alpha + dst[c] as f32 / 255.0 * (1.0 - alpha);
+        out[c] = (value * 255.0).round() as u8;
+    }
+    let value = alpha + dst[3] as f32 / 255.0 * (1.0 - alpha);
+    out[3] = (value * 255.0).round() as u8;
+    target.set_pixel(x, y, out);
+}
+
+#[test]
+fn test_render_window() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().display_size = [200.0, 100.0];
+    let renderer = SoftwareRenderer::new(&mut ctx);
+    let ui = ctx.new_frame();
+    ui.window("Test")
+        .position([10.0, 10.0], crate::Condition::Always)
+        .size([100.0, 50.0], crate::Condition::Always)
+        .build(|| ui.text("Hello"));
+    let image = renderer.render(ctx.render()).unwrap();
+
+    assert_eq!((image.width(), image.height()), (200, 100));
+    // Outside the window nothing was drawn.
+    assert_eq!(image.pixel(5, 5), [0, 0, 0, 0]);
+    assert_eq!(image.pixel(150, 80), [0, 0, 0, 0]);
+    // The window background is blended over the transparent clear colour.
+    let [r, g, b, a] = ctx.style()[crate::StyleColor::WindowBg];
+    let expected = [r * a, g * a, b * a, a].map(|c| (c * 255.0).round() as u8);
+    assert_eq!(image.pixel(60, 45), expected);
+    // The text was sampled from the font atlas.
+    let text_pixels = (10..60)
+        .flat_map(|y| (10..110).map(move |x| (x, y)))
+        .filter(|&(x, y)| image.pixel(x, y)[0] > 200)
+        .count();
+    assert!(text_pixels > 0);
+}
+
+#[test]
+fn test_render_clip_rect_and_texture() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().display_size = [64.0, 64.0];
+    let mut renderer = SoftwareRenderer::new(&mut ctx);
+    let mut checker = RgbaImage::new(2, 1);
+    checker.set_pixel(0, 0, [255, 0, 0, 255]);
+    checker.set_pixel(1, 0, [0, 0, 255, 255]);
+    let texture = renderer.textures_mut().insert(checker);
+
+    let ui = ctx.new_frame();
+    {
+        let draw_list = ui.get_background_draw_list();
+        draw_list.with_clip_rect([0.0, 0.0], [48.0, 64.0], || {
+            draw_list
+                .add_image(texture, [0.0, 0.0], [64.0, 64.0])
+                .col([1.0, 1.0, 1.0, 1.0])
+                .build();
+        });
+    }
+    let image = renderer.render(ctx.render()).unwrap();
+
+    assert_eq!(image.pixel(10, 10), [255, 0, 0, 255]);
+    assert_eq!(image.pixel(40, 10), [0, 0, 255, 255]);
+    // Clipped away.
+    assert_eq!(image.pixel(56, 10), [0, 0, 0, 0]);
+}
+
+#[test]
+fn test_render_bad_texture() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let renderer = SoftwareRenderer::new(&mut ctx);
+    let ui = ctx.new_frame();
+    ui.get_background_draw_list()
+        .add_image(TextureId::new(1234), [0.0, 0.0], [8.0, 8.0])
+        .build();
+    assert_eq!(
+        renderer.render(ctx.render()),
+        Err(SoftwareRendererError::BadTexture(TextureId::new(1234)))
+    );
+}
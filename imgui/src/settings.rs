@@ -0,0 +1,747 @@
+//! Application state stored in the .ini settings next to Dear ImGui's own data.
+//!
+//! A [`SettingsHandler`] registered with [`Context::add_settings_handler`] owns every
+//! `[TypeName][EntryName]` section of its type name. It reads these sections whenever settings
+//! are loaded, and writes them whenever settings are saved, together with the window, table and
+//! dock data of Dear ImGui.
+//!
+//! Settings handlers use the internal API of Dear ImGui, and are only available with the
+//! `docking` feature.
+//!
+//! By default Dear ImGui loads and saves the settings in the file set with
+//! [`Context::set_ini_filename`]. A [`SettingsStorage`] set with
+//! [`Context::set_settings_storage`] replaces this file: the settings of the current profile are
+//! loaded from the storage, and saved to it whenever Dear ImGui reports changes through
+//! `io.want_save_ini_settings`.
+
+#[cfg(feature = "docking")]
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+#[cfg(feature = "docking")]
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::io;
+#[cfg(feature = "docking")]
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+#[cfg(feature = "docking")]
+use crate::sys;
+use crate::Context;
+
+/// Reads and writes application state in the .ini settings.
+///
+/// Each entry is stored as a section of key-value lines:
+///
+/// ```text
+/// [MyPanel][Left]
+/// Filter=foo
+/// Expanded=1,4,7
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # use imgui::{SettingsHandler, SettingsWriter};
+/// # use std::collections::BTreeMap;
+/// #[derive(Default)]
+/// struct Filters(BTreeMap<String, String>);
+///
+/// impl SettingsHandler for Filters {
+///     fn type_name(&self) -> &str {
+///         "Filters"
+///     }
+///     fn read_open(&mut self, _name: &str) -> bool {
+///         true
+///     }
+///     fn read_line(&mut self, name: &str, line: &str) {
+///         if let Some(text) = line.strip_prefix("Text=") {
+///             self.0.insert(name.to_owned(), text.to_owned());
+///         }
+///     }
+///     fn write_all(&mut self, out: &mut SettingsWriter<'_>) {
+///         for (name, text) in &self.0 {
+///             out.entry(name);
+///             out.line(&format!("Text={}", text));
+///         }
+///     }
+/// }
+///
+/// let mut ctx = imgui::Context::create();
+/// # ctx.set_ini_filename(None);
+/// ctx.add_settings_handler(Filters::default());
+/// ctx.load_ini_settings("[Filters][Scene]\nText=light\n");
+/// let filters = ctx.settings_handler::<Filters>().unwrap();
+/// assert_eq!(filters.0["Scene"], "light");
+/// ```
+#[cfg(feature = "docking")]
+pub trait SettingsHandler: 'static {
+    /// The name identifying the sections of this handler, e.g. `"Window"` for `[Window][Debug]`.
+    ///
+    /// It must not change after the handler is registered.
+    fn type_name(&self) -> &str;
+
+    /// Called when all settings are cleared, before loading new settings from scratch.
+    fn clear_all(&mut self) {}
+
+    /// Called for each `[TypeName][name]` section header.
+    ///
+    /// Returns `false` to ignore the lines of this entry.
+    fn read_open(&mut self, name: &str) -> bool;
+
+    /// Called for each line of an entry accepted by [`read_open`](Self::read_open).
+    fn read_line(&mut self, name: &str, line: &str);
+
+    /// Called after all settings were read, to apply them to the application.
+    fn apply_all(&mut self) {}
+
+    /// Called when settings are saved, to write every entry of this handler.
+    fn write_all(&mut self, out: &mut SettingsWriter<'_>);
+}
+
+/// Writes the entries of a [`SettingsHandler`] into the .ini settings.
+#[cfg(feature = "docking")]
+pub struct SettingsWriter<'a> {
+    buf: *mut sys::ImGuiTextBuffer,
+    type_name: &'a str,
+    in_entry: bool,
+    _phantom: PhantomData<&'a mut sys::ImGuiTextBuffer>,
+}
+
+#[cfg(feature = "docking")]
+impl SettingsWriter<'_> {
+    /// Returns the type name of the handler being written.
+    pub fn type_name(&self) -> &str {
+        self.type_name
+    }
+
+    /// Starts a new `[TypeName][name]` entry.
+    pub fn entry(&mut self, name: &str) {
+        self.finish_entry();
+        self.append("[");
+        self.append(self.type_name);
+        self.append("][");
+        self.append(name);
+        self.append("]\n");
+        self.in_entry = true;
+    }
+
+    /// Writes a line in the current entry.
+    ///
+    /// The line must not contain newlines.
+    pub fn line(&mut self, line: &str) {
+        debug_assert!(
+            !line.contains('\n'),
+            "settings lines cannot contain newlines"
+        );
+        self.append(line);
+        self.append("\n");
+    }
+
+    fn finish_entry(&mut self) {
+        if self.in_entry {
+            self.append("\n");
+            self.in_entry = false;
+        }
+    }
+
+    fn append(&mut self, text: &str) {
+        let range = text.as_bytes().as_ptr_range();
+        unsafe {
+            sys::ImGuiTextBuffer_append(self.buf, range.start as *const _, range.end as *const _)
+        }
+    }
+}
+
+/// A settings handler owned by a [`Context`].
+#[cfg(feature = "docking")]
+pub(crate) struct RegisteredHandler {
+    type_name: String,
+    bridge: Box<dyn Any>,
+}
+
+#[cfg(feature = "docking")]
+impl std::fmt::Debug for RegisteredHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegisteredHandler")
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "docking")]
+struct Bridge<H> {
+    handler: H,
+    type_name: CString,
+    // Lines always follow the header of their entry, so only the last opened entry is needed
+    entry: String,
+}
+
+#[cfg(feature = "docking")]
+impl<H: SettingsHandler> Bridge<H> {
+    unsafe fn from_raw<'a>(handler: *mut sys::ImGuiSettingsHandler) -> &'a mut Self {
+        &mut *((*handler).UserData as *mut Self)
+    }
+
+    unsafe extern "C" fn clear_all(
+        _ctx: *mut sys::ImGuiContext,
+        handler: *mut sys::ImGuiSettingsHandler,
+    ) {
+        Self::from_raw(handler).handler.clear_all();
+    }
+
+    unsafe extern "C" fn read_open(
+        _ctx: *mut sys::ImGuiContext,
+        handler: *mut sys::ImGuiSettingsHandler,
+        name: *const std::os::raw::c_char,
+    ) -> *mut std::ffi::c_void {
+        let bridge = Self::from_raw(handler);
+        let name = CStr::from_ptr(name).to_string_lossy();
+        if bridge.handler.read_open(&name) {
+            bridge.entry = name.into_owned();
+            bridge as *mut Self as *mut _
+        } else {
+            std::ptr::null_mut()
+        }
+    }
+
+    unsafe extern "C" fn read_line(
+        _ctx: *mut sys::ImGuiContext,
+        handler: *mut sys::ImGuiSettingsHandler,
+        _entry: *mut std::ffi::c_void,
+        line: *const std::os::raw::c_char,
+    ) {
+        let bridge = Self::from_raw(handler);
+        bridge
+            .handler
+            .read_line(&bridge.entry, &CStr::from_ptr(line).to_string_lossy());
+    }
+
+    unsafe extern "C" fn apply_all(
+        _ctx: *mut sys::ImGuiContext,
+        handler: *mut sys::ImGuiSettingsHandler,
+    ) {
+        Self::from_raw(handler).handler.apply_all();
+    }
+
+    unsafe extern "C" fn write_all(
+        _ctx: *mut sys::ImGuiContext,
+        handler: *mut sys::ImGuiSettingsHandler,
+        buf: *mut sys::ImGuiTextBuffer,
+    ) {
+        let bridge = Self::from_raw(handler);
+        let type_name = bridge.type_name.to_str().unwrap();
+        let mut writer = SettingsWriter {
+            buf,
+            type_name,
+            in_entry: false,
+            _phantom: PhantomData,
+        };
+        bridge.handler.write_all(&mut writer);
+        writer.finish_entry();
+    }
+}
+
+#[cfg(feature = "docking")]
+impl Context {
+    /// Registers a handler storing application state in the .ini settings.
+    ///
+    /// Sections of the handler's type name which were already loaded are not replayed, so
+    /// handlers should be registered before settings are loaded, i.e. before the first frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a handler with the same type name is already registered, including Dear
+    /// ImGui's own `Window`, `Table` and `Docking` handlers.
+    #[doc(alias = "AddSettingsHandler")]
+    pub fn add_settings_handler<H: SettingsHandler>(&mut self, handler: H) {
+        let type_name = handler.type_name().to_owned();
+        let type_name_c = CString::new(type_name.as_str()).expect("type name contains a nul byte");
+        assert!(
+            unsafe { sys::igFindSettingsHandler(type_name_c.as_ptr()) }.is_null(),
+            "a settings handler for {:?} is already registered",
+            type_name
+        );
+        let mut bridge = Box::new(Bridge {
+            handler,
+            type_name: type_name_c,
+            entry: String::new(),
+        });
+        let raw = sys::ImGuiSettingsHandler {
+            TypeName: bridge.type_name.as_ptr(),
+            TypeHash: unsafe { sys::igImHashStr(bridge.type_name.as_ptr(), 0, 0) },
+            ClearAllFn: Some(Bridge::<H>::clear_all),
+            ReadInitFn: None,
+            ReadOpenFn: Some(Bridge::<H>::read_open),
+            ReadLineFn: Some(Bridge::<H>::read_line),
+            ApplyAllFn: Some(Bridge::<H>::apply_all),
+            WriteAllFn: Some(Bridge::<H>::write_all),
+            UserData: &mut *bridge as *mut Bridge<H> as *mut _,
+        };
+        unsafe { sys::igAddSettingsHandler(&raw) };
+        self.settings_handlers
+            .push(RegisteredHandler { type_name, bridge });
+    }
+
+    /// Unregisters the settings handler with the given type name.
+    ///
+    /// Returns `false` if no handler registered with [`add_settings_handler`] has this name.
+    ///
+    /// [`add_settings_handler`]: Self::add_settings_handler
+    #[doc(alias = "RemoveSettingsHandler")]
+    pub fn remove_settings_handler(&mut self, type_name: &str) -> bool {
+        let index = match self
+            .settings_handlers
+            .iter()
+            .position(|h| h.type_name == type_name)
+        {
+            Some(index) => index,
+            None => return false,
+        };
+        let type_name = CString::new(type_name).unwrap();
+        unsafe { sys::igRemoveSettingsHandler(type_name.as_ptr()) };
+        self.settings_handlers.remove(index);
+        true
+    }
+
+    /// Returns the first registered settings handler of type `H`.
+    pub fn settings_handler<H: SettingsHandler>(&self) -> Option<&H> {
+        self.settings_handlers
+            .iter()
+            .find_map(|h| h.bridge.downcast_ref::<Bridge<H>>())
+            .map(|bridge| &bridge.handler)
+    }
+
+    /// Returns the first registered settings handler of type `H`, mutably.
+    ///
+    /// Call [`mark_ini_settings_dirty`](Self::mark_ini_settings_dirty) after changing state
+    /// which should be saved.
+    pub fn settings_handler_mut<H: SettingsHandler>(&mut self) -> Option<&mut H> {
+        self.settings_handlers
+            .iter_mut()
+            .find_map(|h| h.bridge.downcast_mut::<Bridge<H>>())
+            .map(|bridge| &mut bridge.handler)
+    }
+
+    /// Marks the settings as modified, so they are saved after `io.ini_saving_rate` seconds.
+    #[doc(alias = "MarkIniSettingsDirty")]
+    pub fn mark_ini_settings_dirty(&mut self) {
+        unsafe { sys::igMarkIniSettingsDirty_Nil() }
+    }
+
+    /// Clears all settings, calling [`SettingsHandler::clear_all`] on every handler.
+    #[doc(alias = "ClearIniSettings")]
+    pub fn clear_ini_settings(&mut self) {
+        unsafe { sys::igClearIniSettings() }
+    }
+}
+
+/// A place where the .ini settings are persisted, in place of the file handled by Dear ImGui.
+///
+/// Settings are stored per profile, so several layouts can coexist in the same storage.
+pub trait SettingsStorage: 'static {
+    /// Returns the settings saved for `profile`, or `None` if there are none.
+    fn load(&mut self, profile: &str) -> io::Result<Option<String>>;
+
+    /// Saves the settings of `profile`, replacing the previous ones.
+    fn save(&mut self, profile: &str, data: &str) -> io::Result<()>;
+}
+
+/// Settings stored in memory, e.g. for tests.
+///
+/// Clones share the same profiles, so a clone can be kept to inspect the settings saved by a
+/// context.
+#[derive(Clone, Debug, Default)]
+pub struct MemorySettingsStorage {
+    profiles: Rc<RefCell<BTreeMap<String, String>>>,
+}
+
+impl MemorySettingsStorage {
+    /// Creates an empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the settings saved for `profile`.
+    pub fn get(&self, profile: &str) -> Option<String> {
+        self.profiles.borrow().get(profile).cloned()
+    }
+
+    /// Replaces the settings saved for `profile`.
+    pub fn insert(&self, profile: &str, data: &str) {
+        self.profiles
+            .borrow_mut()
+            .insert(profile.to_owned(), data.to_owned());
+    }
+
+    /// Returns the names of all saved profiles, sorted.
+    pub fn profiles(&self) -> Vec<String> {
+        self.profiles.borrow().keys().cloned().collect()
+    }
+}
+
+impl SettingsStorage for MemorySettingsStorage {
+    fn load(&mut self, profile: &str) -> io::Result<Option<String>> {
+        Ok(self.get(profile))
+    }
+
+    fn save(&mut self, profile: &str, data: &str) -> io::Result<()> {
+        self.insert(profile, data);
+        Ok(())
+    }
+}
+
+/// Settings stored as one `<profile>.ini` file per profile in a directory.
+#[derive(Clone, Debug)]
+pub struct FileSettingsStorage {
+    dir: PathBuf,
+}
+
+impl FileSettingsStorage {
+    /// Creates a storage in the given directory, which is created when settings are first saved.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileSettingsStorage { dir: dir.into() }
+    }
+
+    /// Returns the path of the file storing `profile`.
+    ///
+    /// Fails if the profile name is not a plain file name.
+    pub fn path(&self, profile: &str) -> io::Result<PathBuf> {
+        if profile.is_empty()
+            || profile.starts_with('.')
+            || profile.contains(['/', '\\', ':', '\0'])
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid settings profile name {:?}", profile),
+            ));
+        }
+        Ok(self.dir.join(format!("{}.ini", profile)))
+    }
+}
+
+impl SettingsStorage for FileSettingsStorage {
+    fn load(&mut self, profile: &str) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.path(profile)?) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save(&mut self, profile: &str, data: &str) -> io::Result<()> {
+        let path = self.path(profile)?;
+        fs::create_dir_all(&self.dir)?;
+        // Write a temporary file first, so a crash never leaves truncated settings behind
+        let tmp = path.with_extension("ini.tmp");
+        fs::write(&tmp, data)?;
+        fs::rename(tmp, path)
+    }
+}
+
+/// The settings storage of a [`Context`].
+pub(crate) struct StorageState {
+    storage: Box<dyn SettingsStorage>,
+    profile: String,
+    save_delay: f32,
+    since_change: f32,
+    pending: bool,
+    error: Option<io::Error>,
+}
+
+impl std::fmt::Debug for StorageState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StorageState")
+            .field("profile", &self.profile)
+            .field("save_delay", &self.save_delay)
+            .field("pending", &self.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Context {
+    /// Replaces the .ini file with a settings storage, and loads the settings of `profile`.
+    ///
+    /// This disables the .ini file set with [`set_ini_filename`](Self::set_ini_filename). The
+    /// settings are then saved to the storage whenever Dear ImGui reports changes, after
+    /// `io.ini_saving_rate` seconds and the optional
+    /// [save delay](Self::set_settings_save_delay), as well as when the context is dropped.
+    ///
+    /// If a storage was already set, the current settings are saved to it first.
+    pub fn set_settings_storage<S: SettingsStorage>(
+        &mut self,
+        storage: S,
+        profile: &str,
+    ) -> io::Result<()> {
+        if self.settings_storage.is_some() {
+            self.save_settings()?;
+        }
+        self.set_ini_filename(None);
+        self.settings_storage = Some(Box::new(StorageState {
+            storage: Box::new(storage),
+            profile: String::new(),
+            save_delay: 0.0,
+            since_change: 0.0,
+            pending: false,
+            error: None,
+        }));
+        self.load_settings_profile(profile)
+    }
+
+    /// Returns the current settings profile, if a settings storage is set.
+    pub fn settings_profile(&self) -> Option<&str> {
+        self.settings_storage.as_ref().map(|s| s.profile.as_str())
+    }
+
+    /// Saves the settings of the current profile, then loads the settings of `profile`.
+    ///
+    /// Without the `docking` feature, windows which are missing from the new profile keep their
+    /// current settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no settings storage is set.
+    pub fn switch_settings_profile(&mut self, profile: &str) -> io::Result<()> {
+        assert!(
+            self.settings_storage.is_some(),
+            "switch_settings_profile requires a settings storage"
+        );
+        self.save_settings()?;
+        #[cfg(feature = "docking")]
+        self.clear_ini_settings();
+        self.load_settings_profile(profile)
+    }
+
+    /// Delays automatic saves until the settings have not changed for `seconds`, which avoids
+    /// saving repeatedly while e.g. a window is being resized.
+    ///
+    /// The delay is measured in frame time (`io.delta_time`), and is `0.0` by default.
+    pub fn set_settings_save_delay(&mut self, seconds: f32) {
+        if let Some(state) = &mut self.settings_storage {
+            state.save_delay = seconds;
+        }
+    }
+
+    /// Saves the settings of the current profile to the settings storage immediately.
+    ///
+    /// Does nothing if no settings storage is set.
+    pub fn save_settings(&mut self) -> io::Result<()> {
+        if self.settings_storage.is_none() {
+            return Ok(());
+        }
+        let mut data = String::new();
+        self.save_ini_settings(&mut data);
+        let state = self.settings_storage.as_mut().unwrap();
+        state.pending = false;
+        state.storage.save(&state.profile, &data)
+    }
+
+    /// Returns the error of the last failed automatic save, if any.
+    ///
+    /// A failed save is retried the next time the settings change.
+    pub fn take_settings_error(&mut self) -> Option<io::Error> {
+        self.settings_storage.as_mut()?.error.take()
+    }
+
+    fn load_settings_profile(&mut self, profile: &str) -> io::Result<()> {
+        let state = self.settings_storage.as_mut().unwrap();
+        state.profile = profile.to_owned();
+        state.pending = false;
+        if let Some(data) = state.storage.load(profile)? {
+            // An empty slice would make Dear ImGui read a nul-terminated string
+            if !data.is_empty() {
+                self.load_ini_settings(&data);
+            }
+        }
+        Ok(())
+    }
+
+    /// Saves the settings if Dear ImGui requested it. Called at the start of every frame.
+    pub(crate) fn update_settings_storage(&mut self) {
+        if self.settings_storage.is_none() {
+            return;
+        }
+        let delta_time = self.io().delta_time;
+        let changed = std::mem::take(&mut self.io_mut().want_save_ini_settings);
+        let state = self.settings_storage.as_mut().unwrap();
+        if changed {
+            state.pending = true;
+            state.since_change = 0.0;
+        } else {
+            state.since_change += delta_time;
+        }
+        if state.pending && state.since_change >= state.save_delay {
+            if let Err(e) = self.save_settings() {
+                self.settings_storage.as_mut().unwrap().error = Some(e);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "docking"))]
+#[derive(Default)]
+struct TestHandler {
+    entries: Vec<(String, Vec<String>)>,
+    cleared: usize,
+    applied: usize,
+}
+
+#[cfg(all(test, feature = "docking"))]
+impl SettingsHandler for TestHandler {
+    fn type_name(&self) -> &str {
+        "Test"
+    }
+    fn clear_all(&mut self) {
+        self.entries.clear();
+        self.cleared += 1;
+    }
+    fn read_open(&mut self, name: &str) -> bool {
+        if name == "Ignored" {
+            return false;
+        }
+        self.entries.push((name.to_owned(), Vec::new()));
+        true
+    }
+    fn read_line(&mut self, name: &str, line: &str) {
+        let entry = self.entries.last_mut().unwrap();
+        assert_eq!(entry.0, name);
+        entry.1.push(line.to_owned());
+    }
+    fn apply_all(&mut self) {
+        self.applied += 1;
+    }
+    fn write_all(&mut self, out: &mut SettingsWriter<'_>) {
+        for (name, lines) in &self.entries {
+            out.entry(name);
+            for line in lines {
+                out.line(line);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "docking")]
+#[test]
+fn test_settings_handler_round_trip() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    ctx.add_settings_handler(TestHandler::default());
+    ctx.load_ini_settings(
+        "[Test][Left]\nFilter=abc\nExpanded=1,4\n\n[Test][Ignored]\nFoo=1\n\n[Test][Right]\nFilter=\n",
+    );
+    let handler = ctx.settings_handler::<TestHandler>().unwrap();
+    assert_eq!(handler.applied, 1);
+    assert_eq!(
+        handler.entries,
+        vec![
+            (
+                "Left".to_owned(),
+                vec!["Filter=abc".to_owned(), "Expanded=1,4".to_owned()]
+            ),
+            ("Right".to_owned(), vec!["Filter=".to_owned()]),
+        ]
+    );
+
+    let mut buf = String::new();
+    ctx.save_ini_settings(&mut buf);
+    assert!(buf.contains("[Test][Left]\nFilter=abc\nExpanded=1,4\n\n[Test][Right]\nFilter=\n\n"));
+    assert!(!buf.contains("Ignored"));
+
+    ctx.clear_ini_settings();
+    assert_eq!(ctx.settings_handler::<TestHandler>().unwrap().cleared, 1);
+    assert!(ctx
+        .settings_handler_mut::<TestHandler>()
+        .unwrap()
+        .entries
+        .is_empty());
+
+    assert!(ctx.remove_settings_handler("Test"));
+    assert!(!ctx.remove_settings_handler("Test"));
+    assert!(ctx.settings_handler::<TestHandler>().is_none());
+    let mut buf = String::new();
+    ctx.save_ini_settings(&mut buf);
+    assert!(!buf.contains("[Test]"));
+}
+
+#[cfg(feature = "docking")]
+#[test]
+#[should_panic(expected = "already registered")]
+fn test_settings_handler_duplicate() {
+    struct Window;
+    impl SettingsHandler for Window {
+        fn type_name(&self) -> &str {
+            "Window"
+        }
+        fn read_open(&mut self, _: &str) -> bool {
+            false
+        }
+        fn read_line(&mut self, _: &str, _: &str) {}
+        fn write_all(&mut self, _: &mut SettingsWriter<'_>) {}
+    }
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    ctx.add_settings_handler(Window);
+}
+
+#[test]
+fn test_settings_storage() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let storage = MemorySettingsStorage::new();
+    storage.insert("a", "[Window][Stored]\nPos=100,50\nSize=200,100\n");
+    ctx.set_settings_storage(storage.clone(), "a").unwrap();
+    assert_eq!(ctx.settings_profile(), Some("a"));
+    assert_eq!(ctx.ini_filename(), None);
+    ctx.io_mut().ini_saving_rate = 0.01;
+    ctx.set_settings_save_delay(0.1);
+
+    let frame = |ctx: &mut Context, x: f32| {
+        let ui = ctx.new_frame();
+        ui.window("Moved")
+            .position([x, 10.0], crate::Condition::Always)
+            .build(|| ());
+        let pos = ui.window("Stored").build(|| ui.window_pos());
+        ctx.render();
+        pos
+    };
+    assert_eq!(frame(&mut ctx, 0.0), Some([100.0, 50.0]));
+    // Saves are delayed while the window keeps moving
+    for x in 1..20 {
+        frame(&mut ctx, x as f32);
+    }
+    assert!(!storage.get("a").unwrap().contains("[Window][Moved]"));
+    for _ in 0..10 {
+        frame(&mut ctx, 19.0);
+    }
+    assert!(storage
+        .get("a")
+        .unwrap()
+        .contains("[Window][Moved]\nPos=19,10"));
+
+    frame(&mut ctx, 42.0);
+    ctx.switch_settings_profile("b").unwrap();
+    assert_eq!(ctx.settings_profile(), Some("b"));
+    assert!(storage.get("a").unwrap().contains("Pos=42,10"));
+    assert_eq!(storage.profiles(), vec!["a".to_owned()]);
+    drop(ctx);
+    assert_eq!(storage.profiles(), vec!["a".to_owned(), "b".to_owned()]);
+}
+
+#[test]
+fn test_file_settings_storage() {
+    let dir = std::env::temp_dir().join(format!("imgui-settings-{}", std::process::id()));
+    let mut storage = FileSettingsStorage::new(&dir);
+    assert_eq!(storage.load("layout").unwrap(), None);
+    storage.save("layout", "[Window][A]\nPos=1,2\n").unwrap();
+    assert_eq!(
+        storage.load("layout").unwrap().as_deref(),
+        Some("[Window][A]\nPos=1,2\n")
+    );
+    assert!(dir.join("layout.ini").exists());
+    for name in ["", "../x", "a/b", ".hidden"] {
+        assert_eq!(
+            storage.load(name).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -135,7 +135,7 @@ impl StyleStackToken<'_> {
 }
 
 #[inline]
-unsafe fn push_style_var(style_var: StyleVar) {
+pub(crate) unsafe fn push_style_var(style_var: StyleVar) {
     use crate::style::StyleVar::*;
     use crate::sys::{igPushStyleVar_Float, igPushStyleVar_Vec2};
     match style_var {
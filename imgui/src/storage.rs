@@ -0,0 +1,168 @@
+use std::marker::PhantomData;
+use std::os::raw::c_void;
+
+use crate::sys;
+use crate::{Id, Ui};
+
+/// Small values kept by Dear ImGui for the current window, keyed by [`Id`].
+///
+/// This is where widgets keep state between frames, e.g. whether a tree node is open.
+/// Custom widgets can use it for their own state, such as animation timers, instead of a
+/// side table:
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// ui.window("Knobs").build(|| {
+///     let id = ui.new_id_str("knob");
+///     let storage = ui.state_storage();
+///     let turns = storage.get_f32(id, 0.0);
+///     if ui.button("Turn") {
+///         storage.set_f32(id, turns + 0.25);
+///     }
+/// });
+/// ```
+///
+/// The values live as long as the window does, and are not saved to the `.ini` file. All
+/// types share one slot per id, so use different ids for values of different types.
+#[derive(Copy, Clone, Debug)]
+pub struct Storage<'ui> {
+    raw: *mut sys::ImGuiStorage,
+    _ui: PhantomData<&'ui Ui>,
+}
+
+impl Storage<'_> {
+    /// Returns the integer stored under `id`, or `default` if there is none.
+    #[doc(alias = "GetInt")]
+    pub fn get_i32(&self, id: Id, default: i32) -> i32 {
+        unsafe { sys::ImGuiStorage_GetInt(self.raw, id.0, default) }
+    }
+
+    /// Stores an integer under `id`.
+    #[doc(alias = "SetInt")]
+    pub fn set_i32(&self, id: Id, value: i32) {
+        unsafe { sys::ImGuiStorage_SetInt(self.raw, id.0, value) }
+    }
+
+    /// Returns the boolean stored under `id`, or `default` if there is none.
+    ///
+    /// Booleans are stored as integers, so this reads the open state written by tree nodes.
+    #[doc(alias = "GetBool")]
+    pub fn get_bool(&self, id: Id, default: bool) -> bool {
+        unsafe { sys::ImGuiStorage_GetBool(self.raw, id.0, default) }
+    }
+
+    /// Stores a boolean under `id`.
+    #[doc(alias = "SetBool")]
+    pub fn set_bool(&self, id: Id, value: bool) {
+        unsafe { sys::ImGuiStorage_SetBool(self.raw, id.0, value) }
+    }
+
+    /// Returns the float stored under `id`, or `default` if there is none.
+    #[doc(alias = "GetFloat")]
+    pub fn get_f32(&self, id: Id, default: f32) -> f32 {
+        unsafe { sys::ImGuiStorage_GetFloat(self.raw, id.0, default) }
+    }
+
+    /// Stores a float under `id`.
+    #[doc(alias = "SetFloat")]
+    pub fn set_f32(&self, id: Id, value: f32) {
+        unsafe { sys::ImGuiStorage_SetFloat(self.raw, id.0, value) }
+    }
+
+    /// Returns the pointer stored under `id`, or null if there is none.
+    ///
+    /// Dear ImGui never dereferences or frees stored pointers.
+    #[doc(alias = "GetVoidPtr")]
+    pub fn get_ptr(&self, id: Id) -> *mut c_void {
+        unsafe { sys::ImGuiStorage_GetVoidPtr(self.raw, id.0) }
+    }
+
+    /// Stores a pointer under `id`.
+    #[doc(alias = "SetVoidPtr")]
+    // The pointer is only stored, never dereferenced
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    pub fn set_ptr(&self, id: Id, value: *mut c_void) {
+        unsafe { sys::ImGuiStorage_SetVoidPtr(self.raw, id.0, value) }
+    }
+}
+
+impl Ui {
+    /// Returns the state storage of the current window.
+    #[doc(alias = "GetStateStorage")]
+    pub fn state_storage(&self) -> Storage<'_> {
+        Storage {
+            raw: unsafe { sys::igGetStateStorage() },
+            _ui: PhantomData,
+        }
+    }
+
+    /// Sets the id under which the next tree node or collapsing header keeps its open state
+    /// in the window's [`Storage`].
+    ///
+    /// By default the item's own id is used. A shared id lets several items open and close
+    /// together, or the open state be set before the item is submitted.
+    #[doc(alias = "SetNextItemStorageID")]
+    pub fn set_next_item_storage_id(&self, id: Id) {
+        unsafe { sys::igSetNextItemStorageID(id.0) }
+    }
+}
+
+#[test]
+fn test_storage_follows_window() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut ids = None;
+    for frame in 0..2 {
+        let ui = ctx.new_frame();
+        ui.window("a").build(|| {
+            let id = ui.new_id_str("value");
+            let ptr_id = ui.new_id_str("ptr");
+            let storage = ui.state_storage();
+            if frame == 0 {
+                assert_eq!(storage.get_i32(id, -1), -1);
+                assert_eq!(storage.get_ptr(ptr_id), std::ptr::null_mut());
+                storage.set_i32(id, 42);
+                storage.set_ptr(ptr_id, 8 as *mut c_void);
+            } else {
+                assert_eq!(storage.get_i32(id, -1), 42);
+                assert_eq!(storage.get_ptr(ptr_id), 8 as *mut c_void);
+                storage.set_f32(id, 1.5);
+                assert_eq!(storage.get_f32(id, 0.0), 1.5);
+            }
+            ids = Some(id);
+        });
+        ui.window("b").build(|| {
+            let id = ids.unwrap();
+            let storage = ui.state_storage();
+            if frame == 0 {
+                assert!(!storage.get_bool(id, false));
+                storage.set_bool(id, true);
+            } else {
+                assert!(storage.get_bool(id, false));
+            }
+        });
+        ctx.render();
+    }
+}
+
+#[test]
+fn test_next_item_storage_id() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    for frame in 0..2 {
+        let ui = ctx.new_frame();
+        ui.window("tree").build(|| {
+            let id = ui.new_id_str("shared open state");
+            if frame == 1 {
+                ui.state_storage().set_bool(id, true);
+            }
+            ui.set_next_item_storage_id(id);
+            let open = ui.tree_node("node").is_some();
+            assert_eq!(open, frame == 1);
+            // The node's own id is not used for its state
+            let node_id = ui.new_id_str("node");
+            assert!(!ui.state_storage().get_bool(node_id, false));
+        });
+        ctx.render();
+    }
+}
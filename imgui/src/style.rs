@@ -4,7 +4,7 @@ use std::ops::{Index, IndexMut};
 
 use crate::internal::RawCast;
 use crate::Direction;
-use crate::{sys, HoveredFlags};
+use crate::{sys, Context, HoveredFlags};
 
 /// User interface style/colors
 #[repr(C)]
@@ -203,6 +203,72 @@ pub struct Style {
 
 unsafe impl RawCast<sys::ImGuiStyle> for Style {}
 
+// Invokes `$mac` with every `Style` field except `colors`, which callers handle
+// separately. `Style::lerp` builds a struct literal from this list, so a field
+// added upstream fails to compile until it is listed here.
+macro_rules! with_style_fields {
+    ($mac:ident!($($args:tt)*)) => {
+        $mac!($($args)*;
+            alpha,
+            disabled_alpha,
+            window_padding,
+            window_rounding,
+            window_border_size,
+            window_min_size,
+            window_title_align,
+            window_menu_button_position,
+            child_rounding,
+            child_border_size,
+            popup_rounding,
+            popup_border_size,
+            frame_padding,
+            frame_rounding,
+            frame_border_size,
+            item_spacing,
+            item_inner_spacing,
+            cell_padding,
+            touch_extra_padding,
+            indent_spacing,
+            columns_min_spacing,
+            scrollbar_size,
+            scrollbar_rounding,
+            grab_min_size,
+            grab_rounding,
+            log_slider_deadzone,
+            tab_rounding,
+            tab_border_size,
+            tab_min_width_for_close_button,
+            tab_bar_border_size,
+            tab_bar_overline_size,
+            table_angled_headers_angle,
+            table_angled_headers_text_align,
+            color_button_position,
+            button_text_align,
+            selectable_text_align,
+            separator_text_border_size,
+            separator_text_align,
+            separator_text_padding,
+            display_window_padding,
+            display_safe_area_padding,
+            #[cfg(feature = "docking")]
+            docking_separator_size,
+            mouse_cursor_scale,
+            anti_aliased_lines,
+            anti_aliased_lines_use_tex,
+            anti_aliased_fill,
+            curve_tessellation_tol,
+            circle_tesselation_max_error,
+            hover_stationary_delay,
+            hover_delay_short,
+            hover_delay_normal,
+            hover_flags_for_tooltip_mouse,
+            hover_flags_for_tooltip_nav,
+        )
+    };
+}
+#[cfg(feature = "serde")]
+pub(crate) use with_style_fields;
+
 impl Style {
     /// Scales all sizes in the style
     #[doc(alias = "ScaleAllSizes")]
@@ -240,6 +306,28 @@ impl Style {
         }
         self
     }
+
+    /// Blends between two styles, returning `a` at `t = 0.0` and `b` at `t = 1.0`.
+    ///
+    /// Every size and color is interpolated linearly. Fields that can't be
+    /// blended, such as [`Direction`]s, flags and anti-aliasing toggles, switch
+    /// from `a` to `b` halfway through. `t` is clamped to `0.0..=1.0`.
+    pub fn lerp(a: &Style, b: &Style, t: f32) -> Style {
+        macro_rules! lerp_fields {
+            ($a:ident, $b:ident, $t:ident; $($(#[$attr:meta])* $field:ident,)*) => {
+                Style {
+                    $(
+                        $(#[$attr])*
+                        $field: Lerp::lerp($a.$field, $b.$field, $t),
+                    )*
+                    colors: Lerp::lerp($a.colors, $b.colors, $t),
+                }
+            };
+        }
+
+        let t = t.clamp(0.0, 1.0);
+        with_style_fields!(lerp_fields!(a, b, t))
+    }
 }
 
 impl Default for Style {
@@ -596,6 +684,11 @@ impl StyleColor {
         }
     }
 
+    /// Looks up a style color by its [`name`](Self::name).
+    pub fn from_name(name: &str) -> Option<StyleColor> {
+        Self::VARIANTS.iter().copied().find(|c| c.name() == name)
+    }
+
     /// Returns the "Dark" style colors for ImGui as an array.
     ///
     /// You can set this output to [`Style::colors`] to change the style palette.
@@ -912,8 +1005,132 @@ impl fmt::Display for InvalidStyleColorValue {
 }
 impl std::error::Error for InvalidStyleColorValue {}
 
+/// Animates the context style from one [`Style`] to another.
+///
+/// Call [`update`](Self::update) once per frame, before [`Context::new_frame`]. It advances the
+/// transition by [`Io::delta_time`] and writes the blended style to [`Context::style_mut`].
+///
+/// ```no_run
+/// # let mut ctx = imgui::Context::create();
+/// let mut light = imgui::Style::default();
+/// light.use_light_colors();
+/// let mut fade = imgui::StyleTransition::new(*ctx.style(), light, 0.25);
+/// loop {
+///     fade.update(&mut ctx);
+///     let ui = ctx.new_frame();
+///     // ...
+/// #   break;
+/// }
+/// ```
+///
+/// [`Io::delta_time`]: crate::Io::delta_time
+#[derive(Debug, Clone)]
+pub struct StyleTransition {
+    from: Style,
+    to: Style,
+    duration: f32,
+    elapsed: f32,
+    applied: bool,
+}
+
+impl StyleTransition {
+    /// Creates a transition from `from` to `to` lasting `duration` seconds.
+    pub fn new(from: Style, to: Style, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            applied: false,
+        }
+    }
+
+    /// Starts a new transition towards `to` from wherever this one currently is.
+    ///
+    /// This avoids a jump when the target changes before the previous transition finished.
+    pub fn retarget(&mut self, to: Style, duration: f32) {
+        *self = Self::new(self.current(), to, duration);
+    }
+
+    /// Advances the transition by the context's delta time and applies the blended style.
+    ///
+    /// Returns `true` while the transition is still running. Once it has finished, the target
+    /// style is applied one last time and further calls leave the context style untouched, so
+    /// it can be edited freely afterwards.
+    pub fn update(&mut self, ctx: &mut Context) -> bool {
+        if self.applied {
+            return false;
+        }
+        self.elapsed += ctx.io().delta_time;
+        *ctx.style_mut() = self.current();
+        self.applied = self.is_finished();
+        !self.applied
+    }
+
+    /// Progress of the transition, from `0.0` to `1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).min(1.0)
+        }
+    }
+
+    /// Returns `true` once the transition has reached its target style.
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+
+    /// The style at the current point of the transition.
+    pub fn current(&self) -> Style {
+        Style::lerp(&self.from, &self.to, self.progress())
+    }
+
+    /// The style this transition ends on.
+    pub fn target(&self) -> &Style {
+        &self.to
+    }
+}
+
+// Blending used by `Style::lerp`
+trait Lerp: Copy {
+    fn lerp(a: Self, b: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(a: f32, b: f32, t: f32) -> f32 {
+        // Exact at both ends, unlike `a + (b - a) * t`
+        a * (1.0 - t) + b * t
+    }
+}
+
+impl<T: Lerp, const N: usize> Lerp for [T; N] {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        std::array::from_fn(|i| Lerp::lerp(a[i], b[i], t))
+    }
+}
+
+macro_rules! impl_step_lerp {
+    ($($ty:ty),*) => {
+        $(
+            impl Lerp for $ty {
+                fn lerp(a: Self, b: Self, t: f32) -> Self {
+                    if t < 0.5 {
+                        a
+                    } else {
+                        b
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_step_lerp!(bool, Direction, HoveredFlags);
+
 /// A temporary change in user interface style
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum StyleVar {
     /// Global alpha applies to everything
@@ -1027,6 +1244,67 @@ mod tests {
         assert_eq!(style.cell_padding, [58.0, 60.0]);
     }
 
+    #[test]
+    fn test_style_lerp() {
+        let mut a = Style::default();
+        a.use_dark_colors();
+        a.window_rounding = 0.0;
+        a.frame_padding = [2.0, 4.0];
+        let mut b = Style::default();
+        b.use_light_colors();
+        b.window_rounding = 10.0;
+        b.frame_padding = [6.0, 8.0];
+        b.anti_aliased_fill = false;
+        b.color_button_position = Direction::Left;
+
+        assert_eq!(Style::lerp(&a, &b, 0.0), a);
+        assert_eq!(Style::lerp(&a, &b, 1.0), b);
+        assert_eq!(Style::lerp(&a, &b, 2.0), b);
+
+        let mid = Style::lerp(&a, &b, 0.25);
+        assert_eq!(mid.window_rounding, 2.5);
+        assert_eq!(mid.frame_padding, [3.0, 5.0]);
+        assert!(mid.anti_aliased_fill);
+        assert_eq!(mid.color_button_position, Direction::Right);
+        let text = StyleColor::Text as usize;
+        for i in 0..4 {
+            let expected = a.colors[text][i] * 0.75 + b.colors[text][i] * 0.25;
+            assert_eq!(mid.colors[text][i], expected);
+        }
+
+        let late = Style::lerp(&a, &b, 0.75);
+        assert!(!late.anti_aliased_fill);
+        assert_eq!(late.color_button_position, Direction::Left);
+    }
+
+    #[test]
+    fn test_style_transition() {
+        let (_guard, mut ctx) = crate::test::test_ctx();
+        ctx.io_mut().delta_time = 0.1;
+        let from = *ctx.style();
+        let mut to = from;
+        to.alpha = 0.0;
+        to.use_light_colors();
+
+        let mut fade = StyleTransition::new(from, to, 0.4);
+        assert!(fade.update(&mut ctx));
+        assert_eq!(fade.progress(), 0.25);
+        assert_eq!(ctx.style().alpha, 0.75);
+
+        // Retargeting starts from the current blend
+        fade.retarget(from, 0.2);
+        assert!(fade.update(&mut ctx));
+        assert_eq!(ctx.style().alpha, 0.875);
+        assert!(!fade.update(&mut ctx));
+        assert!(fade.is_finished());
+        assert_eq!(*ctx.style(), from);
+
+        // Once finished, the context style is left alone
+        ctx.style_mut().alpha = 0.5;
+        assert!(!fade.update(&mut ctx));
+        assert_eq!(ctx.style().alpha, 0.5);
+    }
+
     #[test]
     fn test_style_color_indexing() {
         let (_guard, ctx) = crate::test::test_ctx();
@@ -1105,6 +1383,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_style_color_from_name() {
+        for &color in StyleColor::VARIANTS.iter() {
+            assert_eq!(StyleColor::from_name(color.name()), Some(color));
+        }
+        assert_eq!(StyleColor::from_name("text"), None);
+    }
+
     #[test]
     fn test_style_color_variant_names() {
         for idx in StyleColor::VARIANTS.iter() {
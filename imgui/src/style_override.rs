@@ -0,0 +1,289 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use crate::fonts::atlas::FontId;
+use crate::internal::RawCast;
+use crate::math::MintVec4;
+use crate::stacks::push_style_var;
+use crate::style::{StyleColor, StyleVar};
+use crate::sys;
+use crate::Ui;
+
+/// A single change applied by a [`StyleOverride`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum StyleOverrideItem {
+    /// Pushes a style color, see [`Ui::push_style_color`]
+    Color(StyleColor, [f32; 4]),
+    /// Pushes a style variable, see [`Ui::push_style_var`]
+    Var(StyleVar),
+    /// Pushes a font, see [`Ui::push_font`]
+    ///
+    /// Fonts only exist at runtime, so a [`StyleOverride`] leaves this item out when it is
+    /// serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Font(FontId),
+    /// Pushes an item width, see [`Ui::push_item_width`]
+    ItemWidth(f32),
+}
+
+/// A reusable set of style changes, pushed together with [`Ui::push_style_override`].
+///
+/// Overrides can be built at runtime, or defined once as a constant:
+///
+/// ```no_run
+/// # use imgui::*;
+/// const DANGER_BUTTON: StyleOverride = StyleOverride::from_static(&[
+///     StyleOverrideItem::Color(StyleColor::Button, [0.7, 0.1, 0.1, 1.0]),
+///     StyleOverrideItem::Color(StyleColor::ButtonHovered, [0.9, 0.2, 0.2, 1.0]),
+///     StyleOverrideItem::Var(StyleVar::FrameRounding(4.0)),
+/// ]);
+///
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let toolbar = StyleOverride::new()
+///     .var(StyleVar::ItemSpacing([2.0, 2.0]))
+///     .var(StyleVar::FramePadding([4.0, 4.0]));
+///
+/// let _toolbar = ui.push_style_override(&toolbar);
+/// {
+///     let _danger = ui.push_style_override(&DANGER_BUTTON);
+///     ui.button("Delete");
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleOverride {
+    items: Cow<'static, [StyleOverrideItem]>,
+}
+
+impl StyleOverride {
+    /// Creates an empty override.
+    pub const fn new() -> Self {
+        Self::from_static(&[])
+    }
+
+    /// Creates an override from a static list of items, usable in constants.
+    pub const fn from_static(items: &'static [StyleOverrideItem]) -> Self {
+        Self {
+            items: Cow::Borrowed(items),
+        }
+    }
+
+    /// Adds a style color change.
+    pub fn color(self, style_color: StyleColor, color: impl Into<MintVec4>) -> Self {
+        let color: MintVec4 = color.into();
+        self.item(StyleOverrideItem::Color(style_color, color.into()))
+    }
+
+    /// Adds a style variable change.
+    pub fn var(self, style_var: StyleVar) -> Self {
+        self.item(StyleOverrideItem::Var(style_var))
+    }
+
+    /// Adds a font change.
+    pub fn font(self, id: FontId) -> Self {
+        self.item(StyleOverrideItem::Font(id))
+    }
+
+    /// Adds an item width change.
+    pub fn item_width(self, item_width: f32) -> Self {
+        self.item(StyleOverrideItem::ItemWidth(item_width))
+    }
+
+    /// Adds an item.
+    pub fn item(mut self, item: StyleOverrideItem) -> Self {
+        self.push(item);
+        self
+    }
+
+    /// Adds an item in place.
+    pub fn push(&mut self, item: StyleOverrideItem) {
+        self.items.to_mut().push(item);
+    }
+
+    /// Returns the items in the order they are pushed.
+    pub fn items(&self) -> &[StyleOverrideItem] {
+        &self.items
+    }
+
+    /// Returns `true` if the override contains no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl FromIterator<StyleOverrideItem> for StyleOverride {
+    fn from_iter<I: IntoIterator<Item = StyleOverrideItem>>(iter: I) -> Self {
+        Self {
+            items: Cow::Owned(iter.into_iter().collect()),
+        }
+    }
+}
+
+impl Extend<StyleOverrideItem> for StyleOverride {
+    fn extend<I: IntoIterator<Item = StyleOverrideItem>>(&mut self, iter: I) {
+        self.items.to_mut().extend(iter);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for StyleOverride {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Font items can't be serialized, and are left out instead of failing the whole list
+        serializer.collect_seq(
+            self.items
+                .iter()
+                .filter(|item| !matches!(item, StyleOverrideItem::Font(_))),
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StyleOverride {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde::Deserialize::deserialize(deserializer).map(|items| Self {
+            items: Cow::Owned(items),
+        })
+    }
+}
+
+impl Ui {
+    /// Pushes every item of a [`StyleOverride`] to its stack.
+    ///
+    /// Returns a `StyleOverrideToken` that pops all of them, in reverse order, when it is
+    /// dropped or `.pop()` is called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the override contains a font that is not in the font atlas. Anything pushed
+    /// before the font is popped again.
+    pub fn push_style_override(&self, style_override: &StyleOverride) -> StyleOverrideToken<'_> {
+        let mut token = StyleOverrideToken {
+            pushed: Vec::with_capacity(style_override.items.len()),
+            _ui: PhantomData,
+        };
+        for item in style_override.items() {
+            match *item {
+                StyleOverrideItem::Color(style_color, color) => unsafe {
+                    sys::igPushStyleColor_Vec4(style_color as i32, color.into());
+                },
+                StyleOverrideItem::Var(style_var) => unsafe { push_style_var(style_var) },
+                StyleOverrideItem::Font(id) => {
+                    let font = self
+                        .fonts()
+                        .get_font(id)
+                        .expect("Font atlas did not contain the given font");
+                    unsafe { sys::igPushFont(font.raw() as *const _ as *mut _) };
+                }
+                StyleOverrideItem::ItemWidth(item_width) => unsafe {
+                    sys::igPushItemWidth(item_width)
+                },
+            }
+            token.pushed.push(*item);
+        }
+        token
+    }
+}
+
+/// Tracks the changes pushed by [`Ui::push_style_override`], which are popped by calling
+/// `.pop()` or by dropping.
+#[must_use]
+pub struct StyleOverrideToken<'ui> {
+    pushed: Vec<StyleOverrideItem>,
+    _ui: PhantomData<&'ui Ui>,
+}
+
+impl StyleOverrideToken<'_> {
+    /// Pops every change made by the override.
+    pub fn pop(self) {
+        // left empty for drop
+    }
+}
+
+impl Drop for StyleOverrideToken<'_> {
+    fn drop(&mut self) {
+        for item in self.pushed.iter().rev() {
+            unsafe {
+                match item {
+                    StyleOverrideItem::Color(..) => sys::igPopStyleColor(1),
+                    StyleOverrideItem::Var(_) => sys::igPopStyleVar(1),
+                    StyleOverrideItem::Font(_) => sys::igPopFont(),
+                    StyleOverrideItem::ItemWidth(_) => sys::igPopItemWidth(),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_style_override_push_pop() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let base = *ctx.style();
+    let font = ctx.fonts().fonts()[0];
+
+    const DANGER: StyleOverride = StyleOverride::from_static(&[
+        StyleOverrideItem::Color(StyleColor::Button, [1.0, 0.0, 0.0, 1.0]),
+        StyleOverrideItem::Var(StyleVar::FrameRounding(4.0)),
+    ]);
+    let toolbar = StyleOverride::new()
+        .var(StyleVar::Alpha(0.5))
+        .color(StyleColor::Text, [0.0, 1.0, 0.0, 1.0])
+        .font(font)
+        .item_width(42.0);
+    assert_eq!(toolbar.items().len(), 4);
+    assert!(StyleOverride::new().is_empty());
+
+    let ui = ctx.new_frame();
+    ui.window("override").build(|| {
+        let width = ui.calc_item_width();
+        let style = || ui.clone_style();
+        {
+            let _toolbar = ui.push_style_override(&toolbar);
+            let _danger = ui.push_style_override(&DANGER);
+            assert_eq!(style()[StyleColor::Button], [1.0, 0.0, 0.0, 1.0]);
+            assert_eq!(style()[StyleColor::Text], [0.0, 1.0, 0.0, 1.0]);
+            assert_eq!(style().frame_rounding, 4.0);
+            assert_eq!(style().alpha, 0.5);
+            assert_eq!(ui.calc_item_width(), 42.0);
+            assert_eq!(ui.current_font().id(), font);
+        }
+        assert_eq!(style()[StyleColor::Button], base[StyleColor::Button]);
+        assert_eq!(style()[StyleColor::Text], base[StyleColor::Text]);
+        assert_eq!(style().frame_rounding, base.frame_rounding);
+        assert_eq!(style().alpha, base.alpha);
+        assert_eq!(ui.calc_item_width(), width);
+    });
+    ctx.render();
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_style_override_serde() {
+    let json = r#"[
+        {"Color": ["ButtonHovered", [0.9, 0.2, 0.2, 1.0]]},
+        {"Var": {"FramePadding": [4.0, 2.0]}},
+        {"ItemWidth": -1.0}
+    ]"#;
+    let style_override: StyleOverride = serde_json::from_str(json).unwrap();
+    let expected = StyleOverride::new()
+        .color(StyleColor::ButtonHovered, [0.9, 0.2, 0.2, 1.0])
+        .var(StyleVar::FramePadding([4.0, 2.0]))
+        .item_width(-1.0);
+    assert_eq!(style_override, expected);
+
+    let text = serde_json::to_string(&expected).unwrap();
+    assert_eq!(
+        serde_json::from_str::<StyleOverride>(&text).unwrap(),
+        expected
+    );
+
+    // Fonts are left out
+    let with_font = expected.clone().font(FontId::default());
+    let text = serde_json::to_string(&with_font).unwrap();
+    assert_eq!(
+        serde_json::from_str::<StyleOverride>(&text).unwrap(),
+        expected
+    );
+    assert!(serde_json::from_str::<StyleOverride>(r#"[{"Font": null}]"#).is_err());
+}
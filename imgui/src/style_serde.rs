@@ -0,0 +1,273 @@
+//! `serde` support for [`Style`] and its related types.
+//!
+//! A style is written as a flat map of its fields, with the colors nested
+//! under `colors` and keyed by [`StyleColor::name`]. Missing entries keep the
+//! value of the style being deserialized into, so a theme file only needs to
+//! list what it changes:
+//!
+//! ```toml
+//! window_rounding = 6.0
+//! frame_padding = [6.0, 4.0]
+//!
+//! [colors]
+//! WindowBg = [0.1, 0.1, 0.12, 1.0]
+//! Button = [0.3, 0.2, 0.5, 1.0]
+//! ```
+//!
+//! Unknown fields and color names are ignored, which lets a file written by a
+//! build with the `docking` feature load in one without it.
+
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::style::with_style_fields;
+use crate::{HoveredFlags, Style, StyleColor};
+
+macro_rules! serialize_fields {
+    ($style:ident, $state:ident; $($(#[$attr:meta])* $field:ident,)*) => {
+        $(
+            $(#[$attr])*
+            $state.serialize_field(stringify!($field), &$style.$field)?;
+        )*
+    };
+}
+
+macro_rules! overlay_field {
+    ($style:ident, $map:ident, $key:ident; $($(#[$attr:meta])* $field:ident,)*) => {
+        match $key.as_str() {
+            $(
+                $(#[$attr])*
+                stringify!($field) => $style.$field = $map.next_value()?,
+            )*
+            "colors" => $map.next_value_seed(ColorsOverlay(&mut $style.colors))?,
+            _ => {
+                $map.next_value::<IgnoredAny>()?;
+            }
+        }
+    };
+}
+
+impl Serialize for Style {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Style", 64)?;
+        with_style_fields!(serialize_fields!(self, state));
+        // Written last so that formats like TOML can emit it as a table.
+        state.serialize_field("colors", &Colors(&self.colors))?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Style {
+    /// Deserializes a style, taking missing fields and colors from
+    /// [`Style::default`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut style = Style::default();
+        style.deserialize_overlay(deserializer)?;
+        Ok(style)
+    }
+}
+
+impl Style {
+    /// Overwrites the fields and colors present in `deserializer`, leaving
+    /// everything else as it is.
+    ///
+    /// This is how partial theme files are applied on top of a base style:
+    ///
+    /// ```no_run
+    /// # fn load(theme: &str) -> Result<(), serde_json::Error> {
+    /// let mut style = imgui::Style::default();
+    /// style.use_light_colors();
+    /// style.deserialize_overlay(&mut serde_json::Deserializer::from_str(theme))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deserialize_overlay<'de, D: Deserializer<'de>>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        StyleOverlay(self).deserialize(deserializer)
+    }
+}
+
+struct StyleOverlay<'a>(&'a mut Style);
+
+impl<'de> DeserializeSeed<'de> for StyleOverlay<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StyleOverlay<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map of style fields")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let style = self.0;
+        while let Some(key) = map.next_key::<String>()? {
+            with_style_fields!(overlay_field!(style, map, key));
+        }
+        Ok(())
+    }
+}
+
+struct Colors<'a>(&'a [[f32; 4]; StyleColor::COUNT]);
+
+impl Serialize for Colors<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(StyleColor::COUNT))?;
+        for color in StyleColor::VARIANTS {
+            map.serialize_entry(color.name(), &self.0[color as usize])?;
+        }
+        map.end()
+    }
+}
+
+struct ColorsOverlay<'a>(&'a mut [[f32; 4]; StyleColor::COUNT]);
+
+impl<'de> DeserializeSeed<'de> for ColorsOverlay<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ColorsOverlay<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map of style color names to RGBA values")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(name) = map.next_key::<String>()? {
+            match StyleColor::from_name(&name) {
+                Some(color) => self.0[color as usize] = map.next_value()?,
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for StyleColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for StyleColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        StyleColor::from_name(&name)
+            .ok_or_else(|| de::Error::custom(format_args!("unknown style color `{}`", name)))
+    }
+}
+
+// bitflags 1 has no serde support, so flags are stored as their raw bits.
+impl Serialize for HoveredFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.bits())
+    }
+}
+
+impl<'de> Deserialize<'de> for HoveredFlags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = u32::deserialize(deserializer)?;
+        HoveredFlags::from_bits(bits)
+            .ok_or_else(|| de::Error::custom(format_args!("invalid hovered flags {:#x}", bits)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    #[test]
+    fn test_style_json_round_trip() {
+        let mut style = Style::default();
+        style.use_classic_colors();
+        style.window_rounding = 7.0;
+        style.color_button_position = Direction::Left;
+        style.hover_flags_for_tooltip_nav = HoveredFlags::DELAY_SHORT;
+        style[StyleColor::TextLink] = [0.1, 0.2, 0.3, 0.4];
+
+        let json = serde_json::to_string(&style).unwrap();
+        assert!(json.contains(r#""TextLink":[0.1,0.2,0.3,0.4]"#));
+        let back: Style = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, style);
+    }
+
+    #[test]
+    fn test_style_toml_round_trip() {
+        let mut style = Style::default();
+        style.use_light_colors();
+        style.frame_padding = [6.0, 2.0];
+
+        let text = toml::to_string(&style).unwrap();
+        assert!(text.contains("\n[colors]\n"));
+        let back: Style = toml::from_str(&text).unwrap();
+        assert_eq!(back, style);
+    }
+
+    #[test]
+    fn test_style_partial_overlay() {
+        let theme = r#"
+            window_rounding = 6.0
+            window_menu_button_position = "Right"
+            unknown_field = "ignored"
+
+            [colors]
+            Button = [0.3, 0.2, 0.5, 1.0]
+            NotAColor = [1.0, 1.0, 1.0, 1.0]
+        "#;
+
+        let mut base = Style::default();
+        base.use_light_colors();
+        let mut style = base;
+        style
+            .deserialize_overlay(toml::Deserializer::new(theme))
+            .unwrap();
+
+        let mut expected = base;
+        expected.window_rounding = 6.0;
+        expected.window_menu_button_position = Direction::Right;
+        expected[StyleColor::Button] = [0.3, 0.2, 0.5, 1.0];
+        assert_eq!(style, expected);
+
+        // Fields missing from a full deserialize come from the default style
+        let style: Style = toml::from_str(theme).unwrap();
+        assert_eq!(
+            style.window_border_size,
+            Style::default().window_border_size
+        );
+        assert_eq!(style[StyleColor::Text], StyleColor::dark_colors()[0]);
+    }
+
+    #[test]
+    fn test_style_color_by_name() {
+        let json = serde_json::to_string(&StyleColor::TabDimmedSelected).unwrap();
+        assert_eq!(json, r#""TabDimmedSelected""#);
+        let color: StyleColor = serde_json::from_str(&json).unwrap();
+        assert_eq!(color, StyleColor::TabDimmedSelected);
+        assert!(serde_json::from_str::<StyleColor>(r#""Nope""#).is_err());
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        assert!(serde_json::from_str::<Style>(r#"{"alpha": "opaque"}"#).is_err());
+        assert!(serde_json::from_str::<Style>(r#"{"colors": {"Text": [1.0]}}"#).is_err());
+        assert!(serde_json::from_str::<HoveredFlags>("4294967295").is_err());
+    }
+}
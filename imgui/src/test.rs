@@ -1,9 +1,9 @@
-use parking_lot::{ReentrantMutex, ReentrantMutexGuard};
+use parking_lot::ReentrantMutexGuard;
 use std::ptr;
 
 use crate::context::Context;
 
-pub static TEST_MUTEX: ReentrantMutex<()> = parking_lot::const_reentrant_mutex(());
+pub(crate) use crate::testing::CONTEXT_LOCK as TEST_MUTEX;
 
 pub fn test_ctx() -> (ReentrantMutexGuard<'static, ()>, Context) {
     let guard = TEST_MUTEX.lock();
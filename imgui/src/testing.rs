@@ -0,0 +1,734 @@
+//! Helpers for snapshot ("golden file") testing of user interface code.
+//!
+//! A [`Harness`] owns a [`Context`] configured for deterministic, headless use and a
+//! [`SoftwareRenderer`]. It runs a UI closure for a number of frames, feeding it the events of an
+//! [`InputScript`], and produces either an [`RgbaImage`] or a textual dump of the final frame's
+//! [`DrawData`]. The result can then be compared against a golden file stored next to the test:
+//!
+//! ```no_run
+//! use imgui::testing::{check_image_golden, Harness, InputScript, Tolerance};
+//! use imgui::MouseButton;
+//!
+//! let mut harness = Harness::new([320.0, 240.0]);
+//! let script = InputScript::new().click(1, [40.0, 50.0], MouseButton::Left);
+//! let image = harness
+//!     .render_image(4, &script, |ui| {
+//!         ui.window("Settings").build(|| {
+//!             ui.button("Apply");
+//!         });
+//!     })
+//!     .unwrap();
+//! check_image_golden("tests/golden/settings.ppm", &image, Tolerance::default()).unwrap();
+//! ```
+//!
+//! Golden files that do not exist yet are written by the check functions and the check passes.
+//! Set the `IMGUI_UPDATE_GOLDEN` environment variable to overwrite existing golden files after an
+//! intentional change. When a check fails, the actual output is written next to the golden file
+//! with an `.actual` suffix (e.g. `settings.actual.ppm`) so it can be inspected.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use parking_lot::{ReentrantMutex, ReentrantMutexGuard};
+
+use crate::math::MintVec2;
+use crate::render::draw_data::DrawData;
+use crate::render::snapshot::DrawDataSnapshot;
+use crate::render::software::{RgbaImage, SoftwareRenderer, SoftwareRendererError};
+use crate::{Context, Key, MouseButton, Ui};
+
+/// Only one Dear ImGui context may exist at a time, so harnesses (and the crate's own tests)
+/// serialize on this lock.
+pub(crate) static CONTEXT_LOCK: ReentrantMutex<()> = parking_lot::const_reentrant_mutex(());
+
+/// Environment variable which, when set, makes the golden checks overwrite existing golden files.
+pub const UPDATE_GOLDEN_ENV: &str = "IMGUI_UPDATE_GOLDEN";
+
+/// A single input event queued through the `Io::add_*_event` functions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InputEvent {
+    /// Mouse moved to an absolute position
+    MousePos([f32; 2]),
+    /// Mouse button pressed (`true`) or released (`false`)
+    MouseButton(MouseButton, bool),
+    /// Mouse wheel scrolled horizontally and/or vertically
+    MouseWheel([f32; 2]),
+    /// Key pressed (`true`) or released (`false`)
+    Key(Key, bool),
+    /// Text character input
+    Char(char),
+    /// Application window gained (`true`) or lost (`false`) focus
+    Focus(bool),
+}
+
+impl InputEvent {
+    /// Queues this event on the context's IO.
+    pub fn apply(self, ctx: &mut Context) {
+        let io = ctx.io_mut();
+        match self {
+            InputEvent::MousePos(pos) => io.add_mouse_pos_event(pos),
+            InputEvent::MouseButton(button, down) => io.add_mouse_button_event(button, down),
+            InputEvent::MouseWheel(wheel) => io.add_mouse_wheel_event(wheel),
+            InputEvent::Key(key, down) => io.add_key_event(key, down),
+            InputEvent::Char(c) => io.add_input_character(c),
+            InputEvent::Focus(focused) => io.add_focus_event(focused),
+        }
+    }
+}
+
+/// Input events scheduled on specific frames.
+///
+/// Frames are counted from the start of each [`Harness::run`] call, and events are queued just
+/// before the frame starts, in the order they were added.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputScript {
+    events: Vec<(usize, InputEvent)>,
+}
+
+impl InputScript {
+    /// Creates an empty script.
+    pub fn new() -> Self {
+        InputScript { events: Vec::new() }
+    }
+
+    /// Schedules an event on the given frame.
+    pub fn event(mut self, frame: usize, event: InputEvent) -> Self {
+        self.events.push((frame, event));
+        self
+    }
+
+    /// Moves the mouse on the given frame.
+    pub fn mouse_pos(self, frame: usize, pos: impl Into<MintVec2>) -> Self {
+        let pos: MintVec2 = pos.into();
+        self.event(frame, InputEvent::MousePos([pos.x, pos.y]))
+    }
+
+    /// Presses or releases a mouse button on the given frame.
+    pub fn mouse_button(self, frame: usize, button: MouseButton, down: bool) -> Self {
+        self.event(frame, InputEvent::MouseButton(button, down))
+    }
+
+    /// Moves the mouse to `pos` and presses `button` on the given frame, then releases it on the
+    /// next frame.
+    pub fn click(self, frame: usize, pos: impl Into<MintVec2>, button: MouseButton) -> Self {
+        self.mouse_pos(frame, pos)
+            .mouse_button(frame, button, true)
+            .mouse_button(frame + 1, button, false)
+    }
+
+    /// Presses or releases a key on the given frame.
+    pub fn key(self, frame: usize, key: Key, down: bool) -> Self {
+        self.event(frame, InputEvent::Key(key, down))
+    }
+
+    /// Presses a key on the given frame and releases it on the next frame.
+    pub fn key_press(self, frame: usize, key: Key) -> Self {
+        self.key(frame, key, true).key(frame + 1, key, false)
+    }
+
+    /// Types text on the given frame.
+    pub fn text(mut self, frame: usize, text: &str) -> Self {
+        self.events
+            .extend(text.chars().map(|c| (frame, InputEvent::Char(c))));
+        self
+    }
+
+    /// Returns the events scheduled on the given frame.
+    pub fn events_at(&self, frame: usize) -> impl Iterator<Item = InputEvent> + '_ {
+        self.events
+            .iter()
+            .filter(move |(f, _)| *f == frame)
+            .map(|(_, event)| *event)
+    }
+
+    /// Returns the number of frames needed to deliver every event of this script.
+    pub fn len_frames(&self) -> usize {
+        self.events.iter().map(|(f, _)| f + 1).max().unwrap_or(0)
+    }
+}
+
+/// A headless context and software renderer for running UI code in tests.
+///
+/// The context does not load or save an ini file, uses a fixed delta time of 1/60s and has its
+/// font atlas built, so that the same code produces the same output on every run. Creating a
+/// harness takes a process-wide lock which is held until the harness is dropped, so tests using
+/// it can safely run on parallel test threads.
+pub struct Harness {
+    ctx: Context,
+    renderer: SoftwareRenderer,
+    frame: usize,
+    // Must be dropped after `ctx`.
+    _lock: ReentrantMutexGuard<'static, ()>,
+}
+
+impl Harness {
+    /// Creates a harness with the given display size.
+    pub fn new(display_size: impl Into<MintVec2>) -> Self {
+        let lock = CONTEXT_LOCK.lock();
+        Self::from_context(Context::create(), display_size, lock)
+    }
+
+    /// Creates a harness around an existing context, e.g. one with custom fonts or style.
+    ///
+    /// The display size, delta time and ini file settings of the context are overwritten.
+    pub fn with_context(ctx: Context, display_size: impl Into<MintVec2>) -> Self {
+        let lock = CONTEXT_LOCK.lock();
+        Self::from_context(ctx, display_size, lock)
+    }
+
+    fn from_context(
+        mut ctx: Context,
+        display_size: impl Into<MintVec2>,
+        lock: ReentrantMutexGuard<'static, ()>,
+    ) -> Self {
+        let display_size: MintVec2 = display_size.into();
+        ctx.set_ini_filename(None);
+        let io = ctx.io_mut();
+        io.display_size = [display_size.x, display_size.y];
+        io.delta_time = 1.0 / 60.0;
+        let mut renderer = SoftwareRenderer::new(&mut ctx);
+        renderer.set_clear_color([0, 0, 0, 255]);
+        Harness {
+            ctx,
+            renderer,
+            frame: 0,
+            _lock: lock,
+        }
+    }
+
+    /// Returns the underlying context.
+    #[inline]
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Returns the underlying context, e.g. to change the style.
+    #[inline]
+    pub fn context_mut(&mut self) -> &mut Context {
+        &mut self.ctx
+    }
+
+    /// Returns the renderer, e.g. to register textures used by the UI.
+    #[inline]
+    pub fn renderer_mut(&mut self) -> &mut SoftwareRenderer {
+        &mut self.renderer
+    }
+
+    /// Returns the total number of frames run by this harness.
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.frame
+    }
+
+    /// Runs `frames` frames, delivering the events of `script`, and returns the draw data of the
+    /// last frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is zero.
+    pub fn run<F>(&mut self, frames: usize, script: &InputScript, ui_fn: F) -> &DrawData
+    where
+        F: FnMut(&Ui),
+    {
+        run_frames(&mut self.ctx, &mut self.frame, frames, script, ui_fn)
+    }
+
+    /// Runs a single frame without input.
+    pub fn run_frame<F>(&mut self, ui_fn: F) -> &DrawData
+    where
+        F: FnOnce(&Ui),
+    {
+        let mut ui_fn = Some(ui_fn);
+        self.run(1, &InputScript::new(), |ui| {
+            if let Some(f) = ui_fn.take() {
+                f(ui)
+            }
+        })
+    }
+
+    /// Like [`run`](Self::run), but rasterizes the last frame.
+    ///
+    /// The image is cleared to opaque black before drawing.
+    pub fn render_image<F>(
+        &mut self,
+        frames: usize,
+        script: &InputScript,
+        ui_fn: F,
+    ) -> Result<RgbaImage, SoftwareRendererError>
+    where
+        F: FnMut(&Ui),
+    {
+        let draw_data = run_frames(&mut self.ctx, &mut self.frame, frames, script, ui_fn);
+        self.renderer.render(draw_data)
+    }
+
+    /// Like [`run`](Self::run), but returns a textual dump of the last frame's draw data.
+    ///
+    /// See [`dump_draw_data`] for the format.
+    pub fn render_dump<F>(&mut self, frames: usize, script: &InputScript, ui_fn: F) -> String
+    where
+        F: FnMut(&Ui),
+    {
+        dump_draw_data(self.run(frames, script, ui_fn))
+    }
+}
+
+impl fmt::Debug for Harness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Harness")
+            .field("renderer", &self.renderer)
+            .field("frame", &self.frame)
+            .finish_non_exhaustive()
+    }
+}
+
+fn run_frames<'ctx, F>(
+    ctx: &'ctx mut Context,
+    frame_counter: &mut usize,
+    frames: usize,
+    script: &InputScript,
+    mut ui_fn: F,
+) -> &'ctx DrawData
+where
+    F: FnMut(&Ui),
+{
+    assert!(frames > 0, "At least one frame must be run");
+    for frame in 0..frames - 1 {
+        run_frame(ctx, frame, script, &mut ui_fn);
+        ctx.render();
+    }
+    run_frame(ctx, frames - 1, script, &mut ui_fn);
+    *frame_counter += frames;
+    ctx.render()
+}
+
+fn run_frame<F>(ctx: &mut Context, frame: usize, script: &InputScript, ui_fn: &mut F)
+where
+    F: FnMut(&Ui),
+{
+    for event in script.events_at(frame) {
+        event.apply(ctx);
+    }
+    let ui = ctx.new_frame();
+    ui_fn(ui);
+}
+
+/// Returns a deterministic, line-based textual description of the draw data.
+///
+/// This is the text form of a [`DrawDataSnapshot`] without vertex hashes; use
+/// [`DrawDataSnapshot::with_hashes`] directly to also catch changes in vertex data.
+pub fn dump_draw_data(draw_data: &DrawData) -> String {
+    DrawDataSnapshot::new(draw_data).to_string()
+}
+
+/// Encodes an image as a binary PPM (`P6`), dropping the alpha channel.
+pub fn encode_ppm(image: &RgbaImage) -> Vec<u8> {
+    let mut out = format!("P6\n{} {}\n255\n", image.width(), image.height()).into_bytes();
+    for pixel in image.pixels().chunks_exact(4) {
+        out.extend_from_slice(&pixel[..3]);
+    }
+    out
+}
+
+/// Decodes a binary PPM (`P6`) with a maximum value of 255. All pixels are opaque.
+pub fn decode_ppm(data: &[u8]) -> Option<RgbaImage> {
+    let mut pos = 0;
+    let mut header = [0u32; 3];
+    if data.get(..2)? != b"P6" {
+        return None;
+    }
+    pos += 2;
+    for value in header.iter_mut() {
+        // Skip whitespace and comments between header fields.
+        loop {
+            match *data.get(pos)? {
+                b'#' => {
+                    while *data.get(pos)? != b'\n' {
+                        pos += 1;
+                    }
+                }
+                c if c.is_ascii_whitespace() => pos += 1,
+                _ => break,
+            }
+        }
+        let start = pos;
+        while data.get(pos)?.is_ascii_digit() {
+            pos += 1;
+        }
+        *value = std::str::from_utf8(&data[start..pos]).ok()?.parse().ok()?;
+    }
+    let [width, height, max] = header;
+    if max != 255 || !data.get(pos)?.is_ascii_whitespace() {
+        return None;
+    }
+    let rgb = data.get(pos + 1..)?;
+    if rgb.len() != width as usize * height as usize * 3 {
+        return None;
+    }
+    let mut pixels = Vec::with_capacity(rgb.len() / 3 * 4);
+    for pixel in rgb.chunks_exact(3) {
+        pixels.extend_from_slice(pixel);
+        pixels.push(255);
+    }
+    RgbaImage::from_raw(width, height, pixels)
+}
+
+/// Encodes an image as an RGBA PNG.
+///
+/// The image data is stored uncompressed, which keeps the encoder dependency-free at the cost of
+/// larger files.
+pub fn encode_png(image: &RgbaImage) -> Vec<u8> {
+    let mut raw = Vec::with_capacity(image.pixels().len() + image.height() as usize);
+    for row in image.pixels().chunks(image.width() as usize * 4) {
+        // Filter type "None"
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        zlib.push(blocks.peek().is_none() as u8);
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&image.width().to_be_bytes());
+    ihdr.extend_from_slice(&image.height().to_be_bytes());
+    // 8 bits per channel, RGBA, default compression/filter, no interlacing
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_png_chunk(&mut out, b"IHDR", &ihdr);
+    write_png_chunk(&mut out, b"IDAT", &zlib);
+    write_png_chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+fn write_png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// How far an image may deviate from its golden file and still match.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tolerance {
+    /// Maximum absolute difference of a colour channel for two pixels to be considered equal
+    pub channel: u8,
+    /// Number of pixels which may differ by more than `channel`
+    pub pixels: usize,
+}
+
+impl Tolerance {
+    /// Requires an exact match.
+    pub const EXACT: Tolerance = Tolerance {
+        channel: 0,
+        pixels: 0,
+    };
+}
+
+/// The result of comparing two images.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImageDiff {
+    /// Number of pixels differing by more than the channel tolerance
+    pub differing_pixels: usize,
+    /// Largest absolute difference of any colour channel
+    pub max_channel_difference: u8,
+}
+
+/// Compares the RGB channels of two images of the same size.
+///
+/// Returns `None` if the sizes differ.
+pub fn compare_images(actual: &RgbaImage, expected: &RgbaImage, channel: u8) -> Option<ImageDiff> {
+    if (actual.width(), actual.height()) != (expected.width(), expected.height()) {
+        return None;
+    }
+    let mut diff = ImageDiff::default();
+    for (a, e) in actual
+        .pixels()
+        .chunks_exact(4)
+        .zip(expected.pixels().chunks_exact(4))
+    {
+        let max = (0..3).map(|c| a[c].abs_diff(e[c])).max().unwrap_or(0);
+        diff.max_channel_difference = diff.max_channel_difference.max(max);
+        if max > channel {
+            diff.differing_pixels += 1;
+        }
+    }
+    Some(diff)
+}
+
+/// An error returned when output does not match its golden file.
+#[derive(Debug)]
+pub enum GoldenError {
+    /// Reading or writing a file failed
+    Io(PathBuf, io::Error),
+    /// The golden file could not be decoded
+    InvalidGolden(PathBuf),
+    /// The output did not match. The actual output was written to the second path.
+    Mismatch {
+        golden: PathBuf,
+        actual: PathBuf,
+        details: String,
+    },
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldenError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            GoldenError::InvalidGolden(path) => {
+                write!(f, "{}: not a valid golden file", path.display())
+            }
+            GoldenError::Mismatch {
+                golden,
+                actual,
+                details,
+            } => write!(
+                f,
+                "output does not match {} ({}); actual output written to {}. Set {} to update.",
+                golden.display(),
+                details,
+                actual.display(),
+                UPDATE_GOLDEN_ENV
+            ),
+        }
+    }
+}
+
+impl Error for GoldenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GoldenError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Compares an image against a golden PPM file.
+///
+/// The golden file is (re)written instead if it does not exist or if the `IMGUI_UPDATE_GOLDEN`
+/// environment variable is set. Alpha is ignored, since PPM files do not store it.
+pub fn check_image_golden(
+    path: impl AsRef<Path>,
+    image: &RgbaImage,
+    tolerance: Tolerance,
+) -> Result<(), GoldenError> {
+    let path = path.as_ref();
+    let encoded = encode_ppm(image);
+    let golden = match read_golden(path, &encoded)? {
+        Some(golden) => golden,
+        None => return Ok(()),
+    };
+    let golden = decode_ppm(&golden).ok_or_else(|| GoldenError::InvalidGolden(path.into()))?;
+    let details = match compare_images(image, &golden, tolerance.channel) {
+        Some(diff) if diff.differing_pixels <= tolerance.pixels => return Ok(()),
+        Some(diff) => format!(
+            "{} pixels differ, by at most {}",
+            diff.differing_pixels, diff.max_channel_difference
+        ),
+        None => format!(
+            "size is {}x{}, expected {}x{}",
+            image.width(),
+            image.height(),
+            golden.width(),
+            golden.height()
+        ),
+    };
+    Err(write_actual(path, &encoded, details))
+}
+
+/// Compares text (e.g. from [`dump_draw_data`]) against a golden text file.
+///
+/// The golden file is (re)written instead if it does not exist or if the `IMGUI_UPDATE_GOLDEN`
+/// environment variable is set.
+pub fn check_text_golden(path: impl AsRef<Path>, text: &str) -> Result<(), GoldenError> {
+    let path = path.as_ref();
+    let golden = match read_golden(path, text.as_bytes())? {
+        Some(golden) => golden,
+        None => return Ok(()),
+    };
+    let golden = String::from_utf8(golden).map_err(|_| GoldenError::InvalidGolden(path.into()))?;
+    if golden == text {
+        return Ok(());
+    }
+    let line = golden
+        .lines()
+        .zip(text.lines())
+        .position(|(g, t)| g != t)
+        .unwrap_or_else(|| golden.lines().count().min(text.lines().count()));
+    let details = format!("first difference on line {}", line + 1);
+    Err(write_actual(path, text.as_bytes(), details))
+}
+
+/// Returns the golden file contents, or `None` if `actual` was written as the new golden file.
+fn read_golden(path: &Path, actual: &[u8]) -> Result<Option<Vec<u8>>, GoldenError> {
+    if std::env::var_os(UPDATE_GOLDEN_ENV).is_none() {
+        match fs::read(path) {
+            Ok(golden) => return Ok(Some(golden)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+            Err(err) => return Err(GoldenError::Io(path.into(), err)),
+        }
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| GoldenError::Io(parent.into(), err))?;
+    }
+    fs::write(path, actual).map_err(|err| GoldenError::Io(path.into(), err))?;
+    Ok(None)
+}
+
+fn write_actual(golden: &Path, actual: &[u8], details: String) -> GoldenError {
+    let mut file_name = golden.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(".actual");
+    if let Some(extension) = golden.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+    let actual_path = golden.with_file_name(file_name);
+    if let Err(err) = fs::write(&actual_path, actual) {
+        return GoldenError::Io(actual_path, err);
+    }
+    GoldenError::Mismatch {
+        golden: golden.into(),
+        actual: actual_path,
+        details,
+    }
+}
+
+#[test]
+fn test_harness_click() {
+    let mut harness = Harness::new([200.0, 100.0]);
+    let mut clicks = 0;
+    let mut button_pos = [0.0, 0.0];
+    harness.run(2, &InputScript::new(), |ui| {
+        ui.window("Test")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .build(|| {
+                ui.button("Click");
+                button_pos = ui.item_rect_min();
+            });
+    });
+    let script = InputScript::new().click(
+        0,
+        [button_pos[0] + 4.0, button_pos[1] + 4.0],
+        MouseButton::Left,
+    );
+    harness.run(3, &script, |ui| {
+        ui.window("Test").build(|| {
+            if ui.button("Click") {
+                clicks += 1;
+            }
+        });
+    });
+    assert_eq!(clicks, 1);
+    assert_eq!(harness.frame_count(), 5);
+}
+
+#[test]
+fn test_dump_is_deterministic() {
+    let ui_fn = |ui: &Ui| {
+        ui.window("Dump").build(|| ui.text("Hello"));
+    };
+    let first = Harness::new([200.0, 100.0]).render_dump(2, &InputScript::new(), ui_fn);
+    let second = Harness::new([200.0, 100.0]).render_dump(2, &InputScript::new(), ui_fn);
+    assert_eq!(first, second);
+    assert!(first.starts_with("draw_data display_pos=0,0 display_size=200,100"));
+    assert!(first.contains("\n  elements count="));
+}
+
+#[test]
+fn test_ppm_round_trip() {
+    let mut image = RgbaImage::new(3, 2);
+    image.fill([10, 20, 30, 255]);
+    image.set_pixel(2, 1, [200, 100, 50, 255]);
+    let encoded = encode_ppm(&image);
+    assert!(encoded.starts_with(b"P6\n3 2\n255\n"));
+    assert_eq!(decode_ppm(&encoded), Some(image));
+    assert_eq!(
+        decode_ppm(b"P6\n# comment\n1 1 255\n\x01\x02\x03")
+            .unwrap()
+            .pixel(0, 0),
+        [1, 2, 3, 255]
+    );
+    assert_eq!(decode_ppm(b"P3\n1 1\n255\n1 2 3"), None);
+}
+
+#[test]
+fn test_png_checksums() {
+    assert_eq!(crc32(b"IEND"), 0xae42_6082);
+    assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    let png = encode_png(&RgbaImage::new(2, 2));
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+}
+
+#[test]
+fn test_image_golden() {
+    let dir = std::env::temp_dir().join(format!("imgui-golden-{}", std::process::id()));
+    let golden = dir.join("image.ppm");
+    let _ = fs::remove_file(&golden);
+
+    let mut image = RgbaImage::new(4, 4);
+    image.fill([0, 0, 0, 255]);
+    // First run records the golden file.
+    check_image_golden(&golden, &image, Tolerance::EXACT).unwrap();
+    assert!(golden.exists());
+    check_image_golden(&golden, &image, Tolerance::EXACT).unwrap();
+
+    image.set_pixel(1, 1, [3, 0, 0, 255]);
+    let tolerance = Tolerance {
+        channel: 2,
+        pixels: 0,
+    };
+    match check_image_golden(&golden, &image, tolerance) {
+        Err(GoldenError::Mismatch { actual, .. }) => {
+            assert_eq!(actual, dir.join("image.actual.ppm"));
+            assert_eq!(decode_ppm(&fs::read(&actual).unwrap()), Some(image.clone()));
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    let tolerance = Tolerance {
+        channel: 3,
+        pixels: 0,
+    };
+    check_image_golden(&golden, &image, tolerance).unwrap();
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,330 @@
+//! Building blocks for writing new interactive widgets.
+//!
+//! The built-in widgets are made of a few steps, which this module exposes: reserve space in
+//! the layout with [`Ui::item_size`], register the item with [`Ui::item_add`], handle the
+//! mouse with [`Ui::button_behavior`] (and [`Ui::drag_behavior`] for values), and draw with
+//! [`Ui::render_frame`] and a [`DrawListMut`](crate::DrawListMut). [`Ui::add_widget`] does
+//! the first three steps at once, so a knob fits in a few lines:
+//!
+//! ```no_run
+//! # use imgui::*;
+//! fn knob(ui: &Ui, label: &str, value: &mut f32) -> bool {
+//!     let id = ui.new_id_str(label);
+//!     let Some(response) = ui.add_widget(id, [40.0, 40.0], ButtonFlags::empty()) else {
+//!         return false;
+//!     };
+//!     let flags = SliderFlags::empty();
+//!     let changed = ui.drag_behavior(id, value, 0.005, Some(0.0), Some(1.0), flags);
+//!
+//!     let color = if response.held {
+//!         StyleColor::FrameBgActive
+//!     } else if response.hovered {
+//!         StyleColor::FrameBgHovered
+//!     } else {
+//!         StyleColor::FrameBg
+//!     };
+//!     let [cx, cy] = response.rect.center();
+//!     let angle = (0.75 + *value * 1.5) * std::f32::consts::PI;
+//!     let draw_list = ui.get_window_draw_list();
+//!     draw_list
+//!         .add_circle([cx, cy], 20.0, ui.style_color(color))
+//!         .filled(true)
+//!         .build();
+//!     draw_list
+//!         .add_line(
+//!             [cx, cy],
+//!             [cx + angle.cos() * 16.0, cy + angle.sin() * 16.0],
+//!             ui.style_color(StyleColor::SliderGrabActive),
+//!         )
+//!         .thickness(2.0)
+//!         .build();
+//!     changed
+//! }
+//! ```
+//!
+//! Ids come from [`Ui::new_id_str`] and the related functions, and are relative to the
+//! current id stack like the labels of built-in widgets.
+//!
+//! Requires the `docking` feature, whose bindings include this part of the internal API.
+
+use std::os::raw::c_void;
+use std::ptr;
+
+use crate::color::ImColor32;
+use crate::internal::DataTypeKind;
+use crate::math::MintVec2;
+use crate::sys;
+use crate::widget::misc::ButtonFlags;
+use crate::widget::slider::SliderFlags;
+use crate::{Id, Ui};
+
+/// A rectangle in screen coordinates.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct WidgetRect {
+    /// The top-left corner
+    pub min: [f32; 2],
+    /// The bottom-right corner
+    pub max: [f32; 2],
+}
+
+impl WidgetRect {
+    /// Creates a rectangle from its top-left and bottom-right corners.
+    pub fn new(min: impl Into<MintVec2>, max: impl Into<MintVec2>) -> Self {
+        WidgetRect {
+            min: min.into().into(),
+            max: max.into().into(),
+        }
+    }
+
+    /// Returns the width and height.
+    pub fn size(&self) -> [f32; 2] {
+        [self.max[0] - self.min[0], self.max[1] - self.min[1]]
+    }
+
+    /// Returns the center point.
+    pub fn center(&self) -> [f32; 2] {
+        [
+            (self.min[0] + self.max[0]) * 0.5,
+            (self.min[1] + self.max[1]) * 0.5,
+        ]
+    }
+
+    /// Returns `true` if the point lies within the rectangle, including its top and left
+    /// edges.
+    pub fn contains(&self, point: impl Into<MintVec2>) -> bool {
+        let point: [f32; 2] = point.into().into();
+        point[0] >= self.min[0]
+            && point[1] >= self.min[1]
+            && point[0] < self.max[0]
+            && point[1] < self.max[1]
+    }
+
+    fn raw(&self) -> sys::ImRect {
+        sys::ImRect {
+            Min: self.min.into(),
+            Max: self.max.into(),
+        }
+    }
+}
+
+/// The mouse interaction with a widget in the current frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct WidgetResponse {
+    /// The area of the widget
+    pub rect: WidgetRect,
+    /// The mouse is over the widget
+    pub hovered: bool,
+    /// The widget is being pressed, e.g. while it is dragged
+    pub held: bool,
+    /// The widget was clicked this frame
+    pub pressed: bool,
+}
+
+/// # Widgets: Custom
+impl Ui {
+    /// Reserves space for an item of the given size at the cursor, and moves the cursor
+    /// past it.
+    #[doc(alias = "ItemSize")]
+    pub fn item_size(&self, size: impl Into<MintVec2>) {
+        unsafe { sys::igItemSize_Vec2(size.into().into(), -1.0) }
+    }
+
+    /// Registers an item covering `rect`, making it the last item for
+    /// [`is_item_hovered`](Self::is_item_hovered) and the related functions.
+    ///
+    /// Returns `false` if the item is clipped, in which case it doesn't need to be drawn or
+    /// interacted with.
+    #[doc(alias = "ItemAdd")]
+    pub fn item_add(&self, rect: WidgetRect, id: Id) -> bool {
+        unsafe { sys::igItemAdd(rect.raw(), id.0, ptr::null(), 0) }
+    }
+
+    /// Handles the mouse for the item covering `rect`, like a button.
+    ///
+    /// A held item is the active item, which lets [`drag_behavior`](Self::drag_behavior)
+    /// change its value. Without any mouse button in `flags`, the left button is used.
+    #[doc(alias = "ButtonBehavior")]
+    pub fn button_behavior(&self, rect: WidgetRect, id: Id, flags: ButtonFlags) -> WidgetResponse {
+        let mut hovered = false;
+        let mut held = false;
+        let pressed = unsafe {
+            sys::igButtonBehavior(
+                rect.raw(),
+                id.0,
+                &mut hovered,
+                &mut held,
+                flags.bits() as i32,
+            )
+        };
+        WidgetResponse {
+            rect,
+            hovered,
+            held,
+            pressed,
+        }
+    }
+
+    /// Reserves space at the cursor and adds an item with button behavior, the common start of
+    /// an interactive widget.
+    ///
+    /// Returns `None` if the item is clipped.
+    pub fn add_widget(
+        &self,
+        id: Id,
+        size: impl Into<MintVec2>,
+        flags: ButtonFlags,
+    ) -> Option<WidgetResponse> {
+        let size: [f32; 2] = size.into().into();
+        let min = self.cursor_screen_pos();
+        let rect = WidgetRect::new(min, [min[0] + size[0], min[1] + size[1]]);
+        self.item_size(size);
+        if !self.item_add(rect, id) {
+            return None;
+        }
+        Some(self.button_behavior(rect, id, flags))
+    }
+
+    /// Changes `value` by `speed` per pixel that the mouse moves while the item is active,
+    /// like a drag slider.
+    ///
+    /// Call this after [`button_behavior`](Self::button_behavior) for the same item, which
+    /// makes the item active while it is held. Returns `true` if the value was changed.
+    #[doc(alias = "DragBehavior")]
+    pub fn drag_behavior<T: DataTypeKind>(
+        &self,
+        id: Id,
+        value: &mut T,
+        speed: f32,
+        min: Option<T>,
+        max: Option<T>,
+        flags: SliderFlags,
+    ) -> bool {
+        unsafe {
+            // The format decides the precision of floats, as in `DragScalar`
+            let format = (*sys::igDataTypeGetInfo(T::KIND as i32)).PrintFmt;
+            let changed = sys::igDragBehavior(
+                id.0,
+                T::KIND as i32,
+                value as *mut T as *mut c_void,
+                speed,
+                min.as_ref().map_or(ptr::null(), |min| min as *const T) as *const c_void,
+                max.as_ref().map_or(ptr::null(), |max| max as *const T) as *const c_void,
+                format,
+                flags.bits() as i32,
+            );
+            if changed {
+                sys::igMarkItemEdited(id.0);
+            }
+            changed
+        }
+    }
+
+    /// Draws a frame like the background of a built-in widget, with the border from the
+    /// current style if `border` is set.
+    #[doc(alias = "RenderFrame")]
+    pub fn render_frame(
+        &self,
+        rect: WidgetRect,
+        color: impl Into<ImColor32>,
+        border: bool,
+        rounding: f32,
+    ) {
+        unsafe {
+            sys::igRenderFrame(
+                rect.min.into(),
+                rect.max.into(),
+                color.into().into(),
+                border,
+                rounding,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Condition, MouseButton};
+
+    #[test]
+    fn test_widget_rect() {
+        let rect = WidgetRect::new([10.0, 20.0], [30.0, 60.0]);
+        assert_eq!(rect.size(), [20.0, 40.0]);
+        assert_eq!(rect.center(), [20.0, 40.0]);
+        assert!(rect.contains([10.0, 20.0]));
+        assert!(!rect.contains([30.0, 40.0]));
+    }
+
+    #[test]
+    fn test_custom_widget_drag() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut value = 0.5f32;
+        let mut responses = Vec::new();
+        // Hover, press, drag right by 20 pixels and release over the widget. The window is
+        // only hovered from its second frame.
+        let mouse = [
+            ([20.0, 50.0], false),
+            ([20.0, 50.0], false),
+            ([20.0, 50.0], true),
+            ([40.0, 50.0], true),
+            ([40.0, 50.0], false),
+        ];
+        for (pos, down) in mouse {
+            ctx.io_mut().add_mouse_pos_event(pos);
+            ctx.io_mut().add_mouse_button_event(MouseButton::Left, down);
+            let ui = ctx.new_frame();
+            ui.window("custom")
+                .position([0.0, 0.0], Condition::Always)
+                .size([200.0, 200.0], Condition::Always)
+                .build(|| {
+                    let id = ui.new_id_str("knob");
+                    let response = ui
+                        .add_widget(id, [40.0, 40.0], ButtonFlags::empty())
+                        .unwrap();
+                    let changed = ui.drag_behavior(
+                        id,
+                        &mut value,
+                        0.01,
+                        Some(0.0),
+                        Some(1.0),
+                        SliderFlags::NO_ROUND_TO_FORMAT,
+                    );
+                    assert_eq!(changed, ui.is_item_edited());
+                    ui.render_frame(response.rect, [1.0, 0.0, 0.0, 1.0], true, 2.0);
+                    responses.push(response);
+
+                    // The cursor moved past the widget
+                    assert_eq!(ui.item_rect_size(), [40.0, 40.0]);
+                    assert!(ui.cursor_screen_pos()[1] >= response.rect.max[1]);
+                });
+            ctx.render();
+        }
+
+        responses.remove(0);
+        assert_eq!(responses[0].rect.size(), [40.0, 40.0]);
+        assert!(responses[0].rect.contains([20.0, 50.0]));
+        assert!(responses.iter().all(|response| response.hovered));
+        let held: Vec<_> = responses.iter().map(|response| response.held).collect();
+        assert_eq!(held, [false, true, true, false]);
+        let pressed: Vec<_> = responses.iter().map(|response| response.pressed).collect();
+        assert_eq!(pressed, [false, false, false, true]);
+        assert!((value - 0.7).abs() < 1e-4, "{}", value);
+    }
+
+    #[test]
+    fn test_custom_widget_clipped() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let ui = ctx.new_frame();
+        ui.window("clipped")
+            .position([0.0, 0.0], Condition::Always)
+            .size([100.0, 100.0], Condition::Always)
+            .build(|| {
+                ui.set_cursor_pos([0.0, 500.0]);
+                let id = ui.new_id_str("hidden");
+                assert!(ui
+                    .add_widget(id, [10.0, 10.0], ButtonFlags::empty())
+                    .is_none());
+            });
+        ctx.render();
+    }
+}
@@ -1,5 +1,7 @@
 pub mod color_editors;
 pub mod combo_box;
+#[cfg(feature = "docking")]
+pub mod custom;
 pub mod drag;
 pub mod image;
 pub mod list_box;
@@ -178,6 +178,10 @@ pub struct Window<'ui, 'a, Label> {
     collapsed_cond: Condition,
     focused: bool,
     bg_alpha: f32,
+    #[cfg(feature = "docking")]
+    dock_id: Option<(crate::DockNodeId, Condition)>,
+    #[cfg(feature = "docking")]
+    window_class: Option<crate::WindowClass>,
 }
 
 impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
@@ -200,6 +204,10 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
             collapsed_cond: Condition::Never,
             focused: false,
             bg_alpha: f32::NAN,
+            #[cfg(feature = "docking")]
+            dock_id: None,
+            #[cfg(feature = "docking")]
+            window_class: None,
         }
     }
     /// Enables the window close button, which sets the passed boolean to false when clicked
@@ -280,6 +288,24 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
         self.bg_alpha = bg_alpha;
         self
     }
+    /// Docks the window into the given dock node, which is applied based on the given
+    /// condition value
+    #[cfg(feature = "docking")]
+    #[doc(alias = "SetNextWindowDockID")]
+    #[inline]
+    pub fn dock_id(mut self, dock_id: impl Into<crate::DockNodeId>, condition: Condition) -> Self {
+        self.dock_id = Some((dock_id.into(), condition));
+        self
+    }
+    /// Sets the window class, which controls which dockspaces and windows this window
+    /// can be docked with.
+    #[cfg(feature = "docking")]
+    #[doc(alias = "SetNextWindowClass")]
+    #[inline]
+    pub fn window_class(mut self, window_class: crate::WindowClass) -> Self {
+        self.window_class = Some(window_class);
+        self
+    }
     /// Enables/disables the title bar.
     ///
     /// Enabled by default.
@@ -523,6 +549,20 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
         if self.bg_alpha.is_finite() {
             unsafe { sys::igSetNextWindowBgAlpha(self.bg_alpha) };
         }
+        #[cfg(feature = "docking")]
+        if let Some((dock_id, cond)) = self.dock_id {
+            if cond != Condition::Never {
+                unsafe { sys::igSetNextWindowDockID(dock_id.id(), cond as i32) };
+            }
+        }
+        #[cfg(feature = "docking")]
+        if let Some(window_class) = &self.window_class {
+            unsafe {
+                sys::igSetNextWindowClass(
+                    window_class as *const crate::WindowClass as *const sys::ImGuiWindowClass,
+                )
+            };
+        }
         let should_render = unsafe {
             sys::igBegin(
                 self.ui.scratch_txt(self.name),
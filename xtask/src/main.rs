@@ -44,10 +44,14 @@ fn lint_all() -> Result<()> {
 }
 
 fn test_all() -> Result<()> {
-    // Test with default/docking/freetype features
+    // Test with default/docking/freetype/automation/remote/serde/system-fonts features
     xshell::cmd!("cargo test --workspace --all-targets").run()?;
     xshell::cmd!("cargo test --workspace --all-targets --features docking").run()?;
     xshell::cmd!("cargo test --workspace --all-targets --features freetype").run()?;
+    xshell::cmd!("cargo test --workspace --all-targets --features automation").run()?;
+    xshell::cmd!("cargo test --workspace --all-targets --features remote").run()?;
+    xshell::cmd!("cargo test --workspace --all-targets --features serde").run()?;
+    xshell::cmd!("cargo test --workspace --all-targets --features system-fonts").run()?;
 
     // Test doc examples
     xshell::cmd!("cargo test --workspace --doc").run()?;